@@ -0,0 +1,97 @@
+use std::fmt;
+
+/// Why a bet was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BetError {
+    // The bet is more than the player's balance.
+    ExceedsBalance,
+    // The bet is zero or negative.
+    NotPositive,
+    // The bet is under the table minimum.
+    BelowMinimum(i32),
+    // The bet is over the table maximum.
+    AboveMaximum(i32),
+}
+
+impl fmt::Display for BetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BetError::ExceedsBalance => write!(f, "You cannot bet more than your balance."),
+            BetError::NotPositive => {
+                write!(f, "Invalid bet amount. Please enter a positive value.")
+            }
+            BetError::BelowMinimum(min) => write!(f, "The table minimum is ${}.", min),
+            BetError::AboveMaximum(max) => write!(f, "The table maximum is ${}.", max),
+        }
+    }
+}
+
+impl std::error::Error for BetError {}
+
+/// The money a player has to bet with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bankroll {
+    balance: i32,
+}
+
+impl Bankroll {
+    pub fn new(balance: i32) -> Bankroll {
+        Bankroll { balance }
+    }
+
+    pub fn balance(&self) -> i32 {
+        self.balance
+    }
+
+    /// Checks that the bankroll can cover the given bet.
+    pub fn check_bet(&self, bet: i32) -> Result<(), BetError> {
+        if bet > self.balance {
+            Err(BetError::ExceedsBalance)
+        } else if bet <= 0 {
+            Err(BetError::NotPositive)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Adds to the balance, which stops at `i32::MAX` rather than overflowing.
+    pub fn deposit(&mut self, amount: i32) {
+        self.balance = self.balance.saturating_add(amount);
+    }
+
+    pub fn withdraw(&mut self, amount: i32) {
+        self.balance -= amount;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_bet() {
+        let bankroll = Bankroll::new(100);
+
+        // Test a valid bet and a bet that is equivalent to the player's balance.
+        assert_eq!(bankroll.check_bet(10), Ok(()));
+        assert_eq!(bankroll.check_bet(100), Ok(()));
+
+        // Test a bet that is less than or equal to 0.
+        assert_eq!(bankroll.check_bet(0), Err(BetError::NotPositive));
+        assert_eq!(bankroll.check_bet(-5), Err(BetError::NotPositive));
+
+        // Test a bet that is greater than the player's balance.
+        assert_eq!(bankroll.check_bet(101), Err(BetError::ExceedsBalance));
+    }
+
+    #[test]
+    fn test_deposit_and_withdraw() {
+        let mut bankroll = Bankroll::new(100);
+        bankroll.withdraw(30);
+        assert_eq!(bankroll.balance(), 70);
+        bankroll.deposit(45);
+        assert_eq!(bankroll.balance(), 115);
+        bankroll.deposit(i32::MAX);
+        assert_eq!(bankroll.balance(), i32::MAX);
+    }
+}
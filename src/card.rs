@@ -0,0 +1,294 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// A card suit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+}
+
+/// A card rank, ordered from Two up to Ace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+
+    /// The rank's value in Blackjack. An Ace counts as 11 here; hands decide when it
+    /// has to count as 1.
+    pub fn value(self) -> i32 {
+        match self {
+            Rank::Two => 2,
+            Rank::Three => 3,
+            Rank::Four => 4,
+            Rank::Five => 5,
+            Rank::Six => 6,
+            Rank::Seven => 7,
+            Rank::Eight => 8,
+            Rank::Nine => 9,
+            // Face cards (Jack, Queen, King) have a value of 10.
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => 10,
+            Rank::Ace => 11,
+        }
+    }
+}
+
+/// A single playing card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl Card {
+    pub fn new(rank: Rank, suit: Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    /// The card's value in Blackjack (e.g., 2-10, 10 for face cards, 11 for an Ace).
+    pub fn value(self) -> i32 {
+        self.rank.value()
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Suit::Clubs => "Clubs",
+            Suit::Diamonds => "Diamonds",
+            Suit::Hearts => "Hearts",
+            Suit::Spades => "Spades",
+        };
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rank::Jack => f.write_str("Jack"),
+            Rank::Queen => f.write_str("Queen"),
+            Rank::King => f.write_str("King"),
+            Rank::Ace => f.write_str("Ace"),
+            number => write!(f, "{}", number.value()),
+        }
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {}", self.rank, self.suit)
+    }
+}
+
+/// Text that does not name a rank, suit or card.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCardError {
+    input: String,
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a valid card", self.input)
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+fn parse_error(input: &str) -> ParseCardError {
+    ParseCardError {
+        input: input.to_string(),
+    }
+}
+
+// Accepts the full name ("Hearts") or its first letter ("H"), in any case.
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Suit, ParseCardError> {
+        match s.trim().to_lowercase().as_str() {
+            "c" | "clubs" => Ok(Suit::Clubs),
+            "d" | "diamonds" => Ok(Suit::Diamonds),
+            "h" | "hearts" => Ok(Suit::Hearts),
+            "s" | "spades" => Ok(Suit::Spades),
+            _ => Err(parse_error(s)),
+        }
+    }
+}
+
+// Accepts "2" to "10" (or "T"), and face cards by name ("King") or letter ("K").
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Rank, ParseCardError> {
+        match s.trim().to_lowercase().as_str() {
+            "2" => Ok(Rank::Two),
+            "3" => Ok(Rank::Three),
+            "4" => Ok(Rank::Four),
+            "5" => Ok(Rank::Five),
+            "6" => Ok(Rank::Six),
+            "7" => Ok(Rank::Seven),
+            "8" => Ok(Rank::Eight),
+            "9" => Ok(Rank::Nine),
+            "10" | "t" => Ok(Rank::Ten),
+            "j" | "jack" => Ok(Rank::Jack),
+            "q" | "queen" => Ok(Rank::Queen),
+            "k" | "king" => Ok(Rank::King),
+            "a" | "ace" => Ok(Rank::Ace),
+            _ => Err(parse_error(s)),
+        }
+    }
+}
+
+// Accepts "Ace of Hearts" or the short form "AH" / "10s".
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Card, ParseCardError> {
+        let s = s.trim();
+        // ASCII lowercasing keeps every byte where it is, so the index is good for `s`.
+        let (rank, suit) = match s.to_ascii_lowercase().find(" of ") {
+            Some(index) => (&s[..index], &s[index + 4..]),
+            None if s.len() >= 2 && s.is_char_boundary(s.len() - 1) => s.split_at(s.len() - 1),
+            None => return Err(parse_error(s)),
+        };
+        match (rank.parse(), suit.parse()) {
+            (Ok(rank), Ok(suit)) => Ok(Card::new(rank, suit)),
+            _ => Err(parse_error(s)),
+        }
+    }
+}
+
+/// Creates a deck of cards (52) in suit and rank order.
+pub fn new_deck() -> Vec<Card> {
+    // Create the deck by combining every rank with every suit.
+    let mut deck: Vec<Card> = Vec::new();
+    for suit in Suit::ALL {
+        for rank in Rank::ALL {
+            deck.push(Card::new(rank, suit));
+        }
+    }
+    deck
+}
+
+/// Creates a deck of cards (52) and shuffles it with the given random number
+/// generator. Pass a seeded generator to get the same deck every time.
+pub fn create_shuffled_deck<R: Rng + ?Sized>(rng: &mut R) -> Vec<Card> {
+    let mut deck = new_deck();
+
+    // Shuffle the deck using the rand crate.
+    deck.shuffle(rng);
+
+    deck
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_create_shuffled_deck() {
+        let original_deck = create_shuffled_deck(&mut StdRng::seed_from_u64(1));
+        let shuffled_deck = create_shuffled_deck(&mut StdRng::seed_from_u64(2));
+
+        // Check if both decks contain the same length of cards.
+        assert_eq!(original_deck.len(), shuffled_deck.len());
+
+        // Different seeds give different decks, and neither is left in order.
+        assert_ne!(original_deck, shuffled_deck);
+        assert_ne!(original_deck, new_deck());
+
+        // The same seed always gives the same deck.
+        assert_eq!(
+            original_deck,
+            create_shuffled_deck(&mut StdRng::seed_from_u64(1))
+        );
+
+        // Every card appears exactly once.
+        let unique: HashSet<Card> = original_deck.iter().copied().collect();
+        assert_eq!(unique.len(), 52);
+    }
+
+    #[test]
+    fn test_rank_values() {
+        let values: Vec<i32> = Rank::ALL.iter().map(|rank| rank.value()).collect();
+        assert_eq!(values, vec![2, 3, 4, 5, 6, 7, 8, 9, 10, 10, 10, 10, 11]);
+        assert_eq!(Card::new(Rank::Queen, Suit::Spades).value(), 10);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(Rank::Two < Rank::Ten);
+        assert!(Rank::King < Rank::Ace);
+        assert!(Suit::Clubs < Suit::Spades);
+        assert!(Card::new(Rank::Two, Suit::Spades) < Card::new(Rank::Three, Suit::Clubs));
+    }
+
+    #[test]
+    fn test_display_and_parse() {
+        for suit in Suit::ALL {
+            for rank in Rank::ALL {
+                let card = Card::new(rank, suit);
+                assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+            }
+        }
+        assert_eq!(
+            Card::new(Rank::Ten, Suit::Hearts).to_string(),
+            "10 of Hearts"
+        );
+        assert_eq!("ah".parse(), Ok(Card::new(Rank::Ace, Suit::Hearts)));
+        assert_eq!("10S".parse(), Ok(Card::new(Rank::Ten, Suit::Spades)));
+        assert_eq!("K of clubs".parse(), Ok(Card::new(Rank::King, Suit::Clubs)));
+
+        // The typo that used to compile as a suit is now rejected.
+        assert!("Diamons".parse::<Suit>().is_err());
+        assert!("10 of Diamons".parse::<Card>().is_err());
+        assert!("1H".parse::<Card>().is_err());
+        assert!("".parse::<Card>().is_err());
+        // Letters that change length when lowercased don't throw the split off.
+        assert!("İİ of x".parse::<Card>().is_err());
+        assert!("Ace OF İ".parse::<Card>().is_err());
+        assert!("İH".parse::<Card>().is_err());
+    }
+}
@@ -0,0 +1,662 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{Game, MAX_SEATS};
+use crate::rules::{Rules, MAX_SPLIT_HANDS};
+use crate::shoe::Shoe;
+use crate::strategy::Strategy;
+
+/// Why a config file could not be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    // The file could not be read.
+    Read {
+        path: String,
+        message: String,
+    },
+    // The file is not valid TOML or JSON, or has fields of the wrong type.
+    Parse {
+        path: String,
+        message: String,
+    },
+    // A setting has a value the game can't be played with.
+    Invalid {
+        field: &'static str,
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read { path, message } => {
+                write!(f, "cannot read config file '{}': {}", path, message)
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "invalid config file '{}': {}", path, message.trim_end())
+            }
+            ConfigError::Invalid { field, message } => {
+                write!(f, "invalid config: {} {}", field, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// What the console game shows besides the play itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayOptions {
+    // Whether to print the rules at the start. When unset the player is asked.
+    pub show_rules: Option<bool>,
+    // Whether to print the shuffle seed so the session can be replayed.
+    pub show_seed: bool,
+    // Whether to print the session's statistics at the end.
+    pub show_stats: bool,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> DisplayOptions {
+        DisplayOptions {
+            show_rules: None,
+            show_seed: true,
+            show_stats: false,
+        }
+    }
+}
+
+/// A named player at the table: a person taking turns at the console, or a
+/// computer-controlled seat-mate when a `strategy` is set. Anything left unset falls
+/// back to the session's `balance` and `spots`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerConfig {
+    pub name: String,
+    pub balance: Option<i32>,
+    pub spots: Option<usize>,
+    // The strategy a computer-controlled player plays.
+    pub strategy: Option<Strategy>,
+    // The flat bet a computer-controlled player places. The table minimum when unset.
+    pub bet: Option<i32>,
+}
+
+impl PlayerConfig {
+    pub fn new(name: impl Into<String>) -> PlayerConfig {
+        PlayerConfig {
+            name: name.into(),
+            ..PlayerConfig::default()
+        }
+    }
+
+    /// A computer-controlled player.
+    pub fn bot(name: impl Into<String>, strategy: Strategy) -> PlayerConfig {
+        PlayerConfig {
+            strategy: Some(strategy),
+            ..PlayerConfig::new(name)
+        }
+    }
+
+    pub fn is_bot(&self) -> bool {
+        self.strategy.is_some()
+    }
+}
+
+/// The settings for a session: the table rules, the starting bankroll, how many spots
+/// the player plays, who sits at the table, the shuffle seed, where the players'
+/// profiles are kept and what the console shows. Every field is optional in a config
+/// file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    // The player's starting balance.
+    pub balance: i32,
+    // How many spots the player plays at once, each with its own bet.
+    pub spots: usize,
+    // The players taking turns at the console, in seat order. When empty a single
+    // player sits down with `balance` and `spots`.
+    pub players: Vec<PlayerConfig>,
+    // The seed for every shuffle. When unset a random seed is picked.
+    pub seed: Option<u64>,
+    // The file the players' profiles are kept in. When unset nothing is kept between
+    // sessions.
+    pub profiles: Option<PathBuf>,
+    pub rules: Rules,
+    pub display: DisplayOptions,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            balance: 100,
+            spots: 1,
+            players: Vec::new(),
+            seed: None,
+            profiles: None,
+            rules: Rules::default(),
+            display: DisplayOptions::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads and validates a config file. Files ending in `.json` are read as JSON and
+    /// anything else as TOML.
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        let name = path.display().to_string();
+        let text = std::fs::read_to_string(path).map_err(|err| ConfigError::Read {
+            path: name.clone(),
+            message: err.to_string(),
+        })?;
+        let parsed = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&text).map_err(|err| err.to_string())
+        } else {
+            toml::from_str(&text).map_err(|err| err.to_string())
+        };
+        let config: Config = parsed.map_err(|message| ConfigError::Parse {
+            path: name,
+            message,
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reads and validates settings written as TOML.
+    pub fn from_toml(text: &str) -> Result<Config, ConfigError> {
+        let config: Config = toml::from_str(text).map_err(|err| ConfigError::Parse {
+            path: "<toml>".to_string(),
+            message: err.to_string(),
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Writes the settings out as TOML, in the same form `load` reads.
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).expect("settings can always be written as TOML")
+    }
+
+    /// The players to seat, in order, with their balance and spots worked out.
+    pub fn table_players(&self) -> Vec<PlayerConfig> {
+        if self.players.is_empty() {
+            return vec![PlayerConfig {
+                balance: Some(self.balance),
+                spots: Some(self.spots),
+                ..PlayerConfig::new("Player")
+            }];
+        }
+        self.players
+            .iter()
+            .map(|player| PlayerConfig {
+                balance: Some(player.balance.unwrap_or(self.balance)),
+                spots: Some(player.spots.unwrap_or(self.spots)),
+                bet: player
+                    .strategy
+                    .map(|_| player.bet.unwrap_or(self.rules.min_bet)),
+                ..player.clone()
+            })
+            .collect()
+    }
+
+    /// Creates a game under these rules, dealt from the given shoe, with every player
+    /// seated on their spots in order.
+    pub fn new_game(&self, shoe: Shoe) -> Game {
+        let mut game = Game::table(shoe);
+        game.set_rules(self.rules);
+        for player in self.table_players() {
+            let index = game.add_player(player.name, player.balance.unwrap_or(self.balance));
+            for _ in 0..player.spots.unwrap_or(1) {
+                game.add_seat(index)
+                    .expect("validated configs have no more spots than seats");
+            }
+        }
+        game
+    }
+
+    /// Checks every setting for a value the game can be played with.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |field, message: String| Err(ConfigError::Invalid { field, message });
+        let rules = &self.rules;
+
+        if self.balance <= 0 {
+            return invalid("balance", format!("must be positive, got {}", self.balance));
+        }
+        if !(1..=MAX_SEATS).contains(&self.spots) {
+            return invalid(
+                "spots",
+                format!("must be between 1 and {}, got {}", MAX_SEATS, self.spots),
+            );
+        }
+        if !(1..=8).contains(&rules.decks) {
+            return invalid(
+                "rules.decks",
+                format!("must be between 1 and 8, got {}", rules.decks),
+            );
+        }
+        if !(1..=100).contains(&rules.penetration_percent) {
+            return invalid(
+                "rules.penetration_percent",
+                format!(
+                    "must be between 1 and 100, got {}",
+                    rules.penetration_percent
+                ),
+            );
+        }
+        if !(1..=MAX_SPLIT_HANDS).contains(&rules.max_split_hands) {
+            return invalid(
+                "rules.max_split_hands",
+                format!(
+                    "must be between 1 (no splitting) and {}, got {}",
+                    MAX_SPLIT_HANDS, rules.max_split_hands
+                ),
+            );
+        }
+        if rules.min_bet <= 0 {
+            return invalid(
+                "rules.min_bet",
+                format!("must be positive, got {}", rules.min_bet),
+            );
+        }
+        if let Some(max) = rules.max_bet {
+            if max < rules.min_bet {
+                return invalid(
+                    "rules.max_bet",
+                    format!(
+                        "must not be below the minimum bet of {}, got {}",
+                        rules.min_bet, max
+                    ),
+                );
+            }
+        }
+        for (index, player) in self.players.iter().enumerate() {
+            if player.name.trim().is_empty() {
+                return invalid("players.name", "must not be empty".to_string());
+            }
+            if self.players[..index]
+                .iter()
+                .any(|other| other.name == player.name)
+            {
+                return invalid(
+                    "players.name",
+                    format!("must be unique, got '{}' twice", player.name),
+                );
+            }
+            if let Some(balance) = player.balance.filter(|&balance| balance < rules.min_bet) {
+                return invalid(
+                    "players.balance",
+                    format!(
+                        "must cover the minimum bet of {}, got {} for {}",
+                        rules.min_bet, balance, player.name
+                    ),
+                );
+            }
+            if let Some(bet) = player.bet {
+                if !player.is_bot() {
+                    return invalid(
+                        "players.bet",
+                        format!(
+                            "is only for computer-controlled players, got one for {}",
+                            player.name
+                        ),
+                    );
+                }
+                if let Err(err) = rules.check_bet(bet) {
+                    return invalid(
+                        "players.bet",
+                        format!(
+                            "must be within the table limits for {}: {}",
+                            player.name, err
+                        ),
+                    );
+                }
+            }
+            if player.spots == Some(0) {
+                return invalid(
+                    "players.spots",
+                    format!("must be at least 1, got 0 for {}", player.name),
+                );
+            }
+        }
+        let seats: usize = self
+            .table_players()
+            .iter()
+            .map(|player| player.spots.unwrap_or(self.spots))
+            .sum();
+        if seats > MAX_SEATS {
+            return invalid(
+                "players",
+                format!("need {} spots, but the table has {}", seats, MAX_SEATS),
+            );
+        }
+        if self.balance < rules.min_bet {
+            return invalid(
+                "balance",
+                format!(
+                    "must cover the minimum bet of {}, got {}",
+                    rules.min_bet, self.balance
+                ),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::{BlackjackPayout, HoleCard, Surrender};
+
+    #[test]
+    fn test_from_toml() {
+        let config = Config::from_toml(
+            r#"
+            balance = 500
+            spots = 3
+            seed = 42
+
+            [rules]
+            decks = 2
+            dealer_hits_soft_17 = true
+            blackjack_payout = "6:5"
+            surrender = "late"
+            hole_card = "no-hole-card"
+            min_bet = 5
+            max_bet = 100
+
+            [display]
+            show_rules = false
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.balance, 500);
+        assert_eq!(config.spots, 3);
+        assert_eq!(config.seed, Some(42));
+        assert_eq!(config.rules.penetration_percent, 75);
+        assert_eq!(
+            config.rules,
+            Rules {
+                decks: 2,
+                dealer_hits_soft_17: true,
+                blackjack_payout: BlackjackPayout::SixToFive,
+                surrender: Surrender::Late,
+                hole_card: HoleCard::NoHoleCard,
+                min_bet: 5,
+                max_bet: Some(100),
+                ..Rules::default()
+            }
+        );
+        assert_eq!(config.display.show_rules, Some(false));
+        assert!(config.display.show_seed);
+
+        // Everything left out keeps its default.
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir();
+        let json = dir.join(format!("blackjack-config-{}.json", std::process::id()));
+        std::fs::write(
+            &json,
+            r#"{"balance": 250, "rules": {"surrender": "early"}}"#,
+        )
+        .unwrap();
+        let config = Config::load(&json).unwrap();
+        std::fs::remove_file(&json).unwrap();
+        assert_eq!(config.balance, 250);
+        assert_eq!(config.rules.surrender, Surrender::Early);
+
+        let toml = dir.join(format!("blackjack-config-{}.toml", std::process::id()));
+        std::fs::write(&toml, Config::default().to_toml()).unwrap();
+        let config = Config::load(&toml).unwrap();
+        std::fs::remove_file(&toml).unwrap();
+        assert_eq!(config, Config::default());
+
+        let missing = dir.join("no-such-blackjack-config.toml");
+        assert!(matches!(
+            Config::load(&missing),
+            Err(ConfigError::Read { .. })
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let config = Config {
+            balance: 1000,
+            spots: 2,
+            players: vec![
+                PlayerConfig::new("Alice"),
+                PlayerConfig {
+                    name: "Bob".to_string(),
+                    balance: Some(300),
+                    spots: Some(1),
+                    ..PlayerConfig::default()
+                },
+            ],
+            seed: Some(7),
+            profiles: Some(PathBuf::from("profiles.toml")),
+            rules: Rules {
+                penetration_percent: 50,
+                max_bet: Some(200),
+                ..Rules::atlantic_city()
+            },
+            display: DisplayOptions {
+                show_rules: Some(true),
+                show_seed: false,
+                show_stats: true,
+            },
+        };
+        assert_eq!(Config::from_toml(&config.to_toml()).unwrap(), config);
+    }
+
+    #[test]
+    fn test_players() {
+        let config = Config::from_toml(
+            r#"
+            balance = 200
+            spots = 2
+
+            [[players]]
+            name = "Alice"
+
+            [[players]]
+            name = "Bob"
+            balance = 50
+            spots = 1
+            "#,
+        )
+        .unwrap();
+
+        // Unset fields fall back to the session's balance and spots.
+        let players = config.table_players();
+        assert_eq!(
+            players,
+            vec![
+                PlayerConfig {
+                    name: "Alice".to_string(),
+                    balance: Some(200),
+                    spots: Some(2),
+                    ..PlayerConfig::default()
+                },
+                PlayerConfig {
+                    name: "Bob".to_string(),
+                    balance: Some(50),
+                    spots: Some(1),
+                    ..PlayerConfig::default()
+                },
+            ]
+        );
+
+        // The game seats the players in order, each on their own spots.
+        let game = config.new_game(Shoe::new(1, 0.75));
+        let seated: Vec<&str> = game
+            .seats()
+            .iter()
+            .map(|seat| game.players()[seat.player()].name())
+            .collect();
+        assert_eq!(seated, vec!["Alice", "Alice", "Bob"]);
+        assert_eq!(game.players()[1].balance(), 50);
+
+        // Without players a single one sits down.
+        let game = Config::default().new_game(Shoe::new(1, 0.75));
+        assert_eq!(game.players().len(), 1);
+        assert_eq!(game.seats().len(), 1);
+        assert_eq!(game.balance(), 100);
+    }
+
+    #[test]
+    fn test_bots() {
+        let config = Config::from_toml(
+            r#"
+            [rules]
+            min_bet = 5
+
+            [[players]]
+            name = "Alice"
+
+            [[players]]
+            name = "Bot"
+            strategy = "mimic-dealer"
+            balance = 500
+            bet = 25
+
+            [[players]]
+            name = "Chart"
+            strategy = "basic"
+            "#,
+        )
+        .unwrap();
+        let players = config.table_players();
+        assert!(!players[0].is_bot());
+        assert_eq!(players[0].bet, None);
+        assert_eq!(players[1].strategy, Some(Strategy::MimicDealer));
+        assert_eq!(players[1].bet, Some(25));
+        // A bot without a bet of its own bets the table minimum.
+        assert_eq!(players[2], {
+            let mut chart = PlayerConfig::bot("Chart", Strategy::Basic);
+            chart.balance = Some(100);
+            chart.spots = Some(1);
+            chart.bet = Some(5);
+            chart
+        });
+
+        // Bots sit and take up seats in the order they are listed.
+        let game = config.new_game(Shoe::new(1, 0.75));
+        assert_eq!(game.seat_label(1), "Bot");
+        assert_eq!(game.players()[1].balance(), 500);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // Typos and values of the wrong kind are reported rather than ignored.
+        for text in [
+            "balanse = 100",
+            "[rules]\ndeck = 6",
+            "balance = \"lots\"",
+            "[rules]\nsurrender = \"sometimes\"",
+            "[rules]\nblackjack_payout = \"2:1\"",
+            "[[players]]\nname = \"Bot\"\nstrategy = \"card-counting\"",
+        ] {
+            let err = Config::from_toml(text).unwrap_err();
+            assert!(matches!(err, ConfigError::Parse { .. }), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        // (config, field, message)
+        let cases = [
+            ("balance = 0", "balance", "must be positive, got 0"),
+            ("spots = 0", "spots", "must be between 1 and 7, got 0"),
+            ("spots = 8", "spots", "must be between 1 and 7, got 8"),
+            (
+                "[rules]\npenetration_percent = 150",
+                "rules.penetration_percent",
+                "must be between 1 and 100, got 150",
+            ),
+            (
+                "[rules]\ndecks = 0",
+                "rules.decks",
+                "must be between 1 and 8, got 0",
+            ),
+            (
+                "[rules]\nmax_split_hands = 0",
+                "rules.max_split_hands",
+                "must be between 1 (no splitting) and 8, got 0",
+            ),
+            (
+                "[rules]\nmax_split_hands = 12",
+                "rules.max_split_hands",
+                "must be between 1 (no splitting) and 8, got 12",
+            ),
+            (
+                "[rules]\nmin_bet = -5",
+                "rules.min_bet",
+                "must be positive, got -5",
+            ),
+            (
+                "[rules]\nmin_bet = 10\nmax_bet = 5",
+                "rules.max_bet",
+                "must not be below the minimum bet of 10, got 5",
+            ),
+            (
+                "[[players]]\nname = \"\"",
+                "players.name",
+                "must not be empty",
+            ),
+            (
+                "[[players]]\nname = \"Alice\"\n[[players]]\nname = \"Alice\"",
+                "players.name",
+                "must be unique, got 'Alice' twice",
+            ),
+            (
+                "[rules]\nmin_bet = 10\n[[players]]\nname = \"Bob\"\nbalance = 5",
+                "players.balance",
+                "must cover the minimum bet of 10, got 5 for Bob",
+            ),
+            (
+                "[[players]]\nname = \"Bob\"\nbet = 10",
+                "players.bet",
+                "is only for computer-controlled players, got one for Bob",
+            ),
+            (
+                "[rules]\nmin_bet = 10\n[[players]]\nname = \"Bot\"\nstrategy = \"basic\"\nbet = 5",
+                "players.bet",
+                "must be within the table limits for Bot: The table minimum is $10.",
+            ),
+            (
+                "[[players]]\nname = \"Bob\"\nspots = 0",
+                "players.spots",
+                "must be at least 1, got 0 for Bob",
+            ),
+            (
+                "spots = 3\n[[players]]\nname = \"Alice\"\n[[players]]\nname = \"Bob\"\n[[players]]\nname = \"Carol\"",
+                "players",
+                "need 9 spots, but the table has 7",
+            ),
+            (
+                "balance = 20\n[rules]\nmin_bet = 25",
+                "balance",
+                "must cover the minimum bet of 25, got 20",
+            ),
+        ];
+        for (text, field, message) in cases {
+            let err = Config::from_toml(text).unwrap_err();
+            assert_eq!(
+                err,
+                ConfigError::Invalid {
+                    field,
+                    message: message.to_string(),
+                },
+                "{}",
+                text
+            );
+            assert_eq!(
+                err.to_string(),
+                format!("invalid config: {} {}", field, message)
+            );
+        }
+    }
+}
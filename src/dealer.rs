@@ -0,0 +1,260 @@
+use crate::card::{Card, Rank};
+use crate::hand::{Hand, HandValue};
+use crate::rules::Rules;
+use crate::shoe::Shoe;
+
+/// The dealer's hand. The first card dealt is the upcard and the second is the hole
+/// card, which stays face down until the dealer reveals it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DealerHand {
+    hand: Hand,
+    revealed: bool,
+}
+
+impl DealerHand {
+    pub fn new() -> DealerHand {
+        DealerHand::default()
+    }
+
+    pub fn hand(&self) -> &Hand {
+        &self.hand
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        self.hand.cards()
+    }
+
+    pub fn len(&self) -> usize {
+        self.hand.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hand.is_empty()
+    }
+
+    pub fn value(&self) -> HandValue {
+        self.hand.value()
+    }
+
+    pub fn upcard(&self) -> Option<Card> {
+        self.hand.cards().first().copied()
+    }
+
+    pub fn hole_card(&self) -> Option<Card> {
+        self.hand.cards().get(1).copied()
+    }
+
+    /// Whether the hole card has been turned over.
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    /// The cards the players can see: only the upcard until the hole card is revealed.
+    pub fn visible_cards(&self) -> &[Card] {
+        if self.revealed {
+            self.hand.cards()
+        } else {
+            &self.hand.cards()[..self.hand.len().min(1)]
+        }
+    }
+
+    /// Turns the hole card face up.
+    pub fn reveal(&mut self) {
+        self.revealed = true;
+    }
+
+    /// Looks at the hole card for a blackjack. The dealer only peeks when showing an Ace
+    /// or a ten-value card, so `None` means there was no peek.
+    pub fn peek(&self) -> Option<bool> {
+        let upcard = self.upcard()?;
+        if upcard.rank == Rank::Ace || upcard.value() == 10 {
+            Some(self.hand.value().is_blackjack)
+        } else {
+            None
+        }
+    }
+
+    pub fn push(&mut self, card: Card) {
+        self.hand.push(card);
+    }
+
+    pub fn clear(&mut self) {
+        self.hand.clear();
+        self.revealed = false;
+    }
+
+    /// Whether the dealer has to draw another card: below 17 always, and on a soft 17
+    /// (e.g. Ace-6 or Ace-Ace-5) if the table has the dealer hit soft 17.
+    pub fn should_hit(&self, rules: &Rules) -> bool {
+        let value = self.hand.value();
+        value.total < 17 || (value.total == 17 && value.soft && rules.dealer_hits_soft_17)
+    }
+
+    /// Plays the dealer's turn (hit or stand according to rules).
+    pub fn play(&mut self, shoe: &mut Shoe, rules: &Rules) {
+        // The dealer draws until the hand stands; their turn ends.
+        while self.should_hit(rules) {
+            // Dealer hits (draws a card from the shoe).
+            match shoe.draw() {
+                Some(card) => self.hand.push(card),
+                None => break,
+            }
+        }
+    }
+}
+
+impl From<Vec<Card>> for DealerHand {
+    fn from(cards: Vec<Card>) -> DealerHand {
+        DealerHand {
+            hand: Hand::from(cards),
+            revealed: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::{Rank, Suit};
+
+    #[test]
+    fn test_dealer_turn() {
+        let mut shoe = Shoe::new(1, 0.75);
+        let rules = Rules::default();
+        // Test the dealer's turn with a hand that needs to hit.
+        let mut dealer_hand = DealerHand::from(vec![
+            Card::new(Rank::Ten, Suit::Hearts),
+            Card::new(Rank::Six, Suit::Spades),
+        ]);
+        dealer_hand.play(&mut shoe, &rules);
+
+        assert!(dealer_hand.value().total >= 17);
+
+        // Test the dealer's turn with a hand that should stand.
+        let mut dealer_hand = DealerHand::from(vec![
+            Card::new(Rank::Ten, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Spades),
+        ]);
+        dealer_hand.play(&mut shoe, &rules);
+        assert_eq!(dealer_hand.value().total, 17);
+
+        // Test the dealer's turn with a hand that already meets the threshold.
+        let mut dealer_hand = DealerHand::from(vec![
+            Card::new(Rank::Ten, Suit::Hearts),
+            Card::new(Rank::Eight, Suit::Spades),
+        ]);
+        dealer_hand.play(&mut shoe, &rules);
+        assert_eq!(dealer_hand.value().total, 18);
+
+        // Test the dealer's turn with a soft 17, which stands.
+        let mut dealer_hand = DealerHand::from(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Six, Suit::Spades),
+        ]);
+        dealer_hand.play(&mut shoe, &rules);
+        assert_eq!(dealer_hand.len(), 2);
+
+        // Test a hand with two Aces that needs to count both as 1 and keep hitting.
+        let mut dealer_hand = DealerHand::from(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::King, Suit::Clubs),
+        ]);
+        dealer_hand.play(&mut shoe, &rules);
+        assert!(dealer_hand.len() > 3);
+        assert!(dealer_hand.value().total >= 17);
+    }
+
+    #[test]
+    fn test_soft_17() {
+        let h17 = Rules {
+            dealer_hits_soft_17: true,
+            ..Rules::default()
+        };
+        let s17 = Rules::default();
+        let dealer = |names: &str| {
+            let cards: Vec<Card> = names
+                .split_whitespace()
+                .map(|name| name.parse().unwrap())
+                .collect();
+            DealerHand::from(cards)
+        };
+
+        // (cards, hits under S17, hits under H17)
+        let cases = [
+            // Soft 17s: an Ace counted as 11.
+            ("AH 6S", false, true),
+            ("AH AS 5C", false, true),
+            ("AH 2S 4C", false, true),
+            ("AH AS AC 4D", false, true),
+            // Hard 17s, including ones with an Ace counted as 1.
+            ("10H 7S", false, false),
+            ("10H 6S AC", false, false),
+            ("AH 6S 10C", false, false),
+            // Anything under 17 hits and anything over stands.
+            ("AH 5S", true, true),
+            ("10H 6S", true, true),
+            ("AH 7S", false, false),
+            ("AH AS 6C", false, false),
+        ];
+        for (cards, hits_s17, hits_h17) in cases {
+            let hand = dealer(cards);
+            assert_eq!(hand.should_hit(&s17), hits_s17, "{} under S17", cards);
+            assert_eq!(hand.should_hit(&h17), hits_h17, "{} under H17", cards);
+        }
+
+        // Under H17 the dealer draws to A-6 and stops on the hard 17 it makes.
+        let mut hand = dealer("AH 6S");
+        hand.play(&mut crate::shoe::stacked(&["10D", "5H"]), &h17);
+        assert_eq!(hand.len(), 3);
+        assert_eq!(hand.value().total, 17);
+        assert!(!hand.value().soft);
+
+        let mut hand = dealer("AH 6S");
+        hand.play(&mut crate::shoe::stacked(&["5H"]), &s17);
+        assert_eq!(hand.len(), 2);
+    }
+
+    #[test]
+    fn test_upcard() {
+        let mut dealer_hand = DealerHand::new();
+        assert_eq!(dealer_hand.upcard(), None);
+        dealer_hand.push(Card::new(Rank::Nine, Suit::Clubs));
+        dealer_hand.push(Card::new(Rank::Ace, Suit::Clubs));
+        assert_eq!(
+            dealer_hand.upcard(),
+            Some(Card::new(Rank::Nine, Suit::Clubs))
+        );
+        assert_eq!(
+            dealer_hand.hole_card(),
+            Some(Card::new(Rank::Ace, Suit::Clubs))
+        );
+
+        // The hole card stays hidden until it is revealed.
+        assert_eq!(dealer_hand.visible_cards().len(), 1);
+        dealer_hand.reveal();
+        assert_eq!(dealer_hand.visible_cards().len(), 2);
+        dealer_hand.clear();
+        assert!(!dealer_hand.is_revealed());
+    }
+
+    #[test]
+    fn test_peek() {
+        let dealer = |upcard: Rank, hole: Rank| {
+            DealerHand::from(vec![
+                Card::new(upcard, Suit::Hearts),
+                Card::new(hole, Suit::Spades),
+            ])
+        };
+
+        // The dealer peeks under an Ace or any ten-value card.
+        assert_eq!(dealer(Rank::Ace, Rank::King).peek(), Some(true));
+        assert_eq!(dealer(Rank::Queen, Rank::Ace).peek(), Some(true));
+        assert_eq!(dealer(Rank::Ten, Rank::Nine).peek(), Some(false));
+        assert_eq!(dealer(Rank::Ace, Rank::Ace).peek(), Some(false));
+
+        // No peek is made under any other upcard.
+        assert_eq!(dealer(Rank::Nine, Rank::Two).peek(), None);
+        assert_eq!(DealerHand::new().peek(), None);
+    }
+}
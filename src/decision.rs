@@ -0,0 +1,754 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{BufRead, StdinLock, Stdout, Write};
+use std::rc::Rc;
+
+use crate::game::Game;
+use crate::round::{Action, Round};
+use crate::strategy::Strategy;
+
+/// Something that makes the player's choices: a person at a console, a script or a bot.
+///
+/// A round asks on behalf of the seat whose turn it is; `round.seat_index()` and
+/// `round.player()` tell which one.
+pub trait DecisionSource {
+    /// Chooses the bet for the seat, or `None` to sit the round out. At a single-player
+    /// table sitting out leaves the table.
+    fn choose_bet(&mut self, round: &Round) -> Option<i32>;
+
+    /// Chooses what to do on the player's turn. The action should be one of
+    /// `round.legal_actions()`.
+    fn choose_action(&mut self, round: &Round) -> Action;
+
+    /// Chooses how much insurance to take when the dealer shows an Ace: any amount from
+    /// 1 up to the one `round.legal_actions()` offers, which is half of the bet. `None`
+    /// declines, as it does by default.
+    fn take_insurance(&mut self, _round: &Round) -> Option<i32> {
+        None
+    }
+
+    /// Answers whether to take even money on a natural when the dealer shows an Ace.
+    /// Declines by default.
+    fn take_even_money(&mut self, _round: &Round) -> bool {
+        false
+    }
+
+    /// Answers whether to surrender early, before the dealer checks for blackjack. By
+    /// default `choose_action` decides, with `Surrender` and `DeclineSurrender` to
+    /// choose from.
+    fn surrender_early(&mut self, round: &Round) -> bool {
+        self.choose_action(round) == Action::Surrender
+    }
+
+    /// Answers whether the player with the given index plays another round.
+    fn keep_playing(&mut self, game: &Game, player: usize) -> bool;
+}
+
+// A shared source can answer for several players, e.g. everyone taking turns at one
+// console.
+impl<T: DecisionSource> DecisionSource for Rc<RefCell<T>> {
+    fn choose_bet(&mut self, round: &Round) -> Option<i32> {
+        self.borrow_mut().choose_bet(round)
+    }
+
+    fn choose_action(&mut self, round: &Round) -> Action {
+        self.borrow_mut().choose_action(round)
+    }
+
+    fn take_insurance(&mut self, round: &Round) -> Option<i32> {
+        self.borrow_mut().take_insurance(round)
+    }
+
+    fn take_even_money(&mut self, round: &Round) -> bool {
+        self.borrow_mut().take_even_money(round)
+    }
+
+    fn surrender_early(&mut self, round: &Round) -> bool {
+        self.borrow_mut().surrender_early(round)
+    }
+
+    fn keep_playing(&mut self, game: &Game, player: usize) -> bool {
+        self.borrow_mut().keep_playing(game, player)
+    }
+}
+
+// Lists the player's choices for a prompt, e.g. "'hit', 'stand' or 'double'". Outside
+// of the player's turn the usual "'hit' or 'stand'" is shown.
+fn describe_choices(actions: &[Action]) -> String {
+    let mut names: Vec<String> = actions
+        .iter()
+        .map(|action| format!("'{}'", action))
+        .collect();
+    if names.is_empty() {
+        names = vec!["'hit'".to_string(), "'stand'".to_string()];
+    }
+    let last = names.pop().unwrap_or_default();
+    if names.is_empty() {
+        last
+    } else {
+        format!("{} or {}", names.join(", "), last)
+    }
+}
+
+/// Asks a person for every decision through a text console.
+pub struct ConsoleDecisions<R, W> {
+    input: R,
+    output: W,
+}
+
+impl ConsoleDecisions<StdinLock<'static>, Stdout> {
+    /// Reads decisions from stdin and writes prompts to stdout.
+    pub fn stdin() -> Self {
+        ConsoleDecisions::new(std::io::stdin().lock(), std::io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> ConsoleDecisions<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        ConsoleDecisions { input, output }
+    }
+
+    pub fn into_output(self) -> W {
+        self.output
+    }
+
+    fn say(&mut self, text: &str) {
+        writeln!(self.output, "{}", text).expect("Failed to write output");
+    }
+
+    // Reads one trimmed, lowercased line, or `None` once the input is closed.
+    fn read_choice(&mut self) -> Option<String> {
+        self.output.flush().expect("Failed to write output");
+        let mut input = String::new();
+        let read = self
+            .input
+            .read_line(&mut input)
+            .expect("Failed to read input");
+        if read == 0 {
+            None
+        } else {
+            Some(input.trim().to_lowercase())
+        }
+    }
+
+    // When several seats are in play, says whose question comes next, e.g. "Spot 2: ".
+    fn seat_prefix(round: &Round) -> String {
+        if round.seats().len() > 1 {
+            format!("{}: ", round.seat_label())
+        } else {
+            String::new()
+        }
+    }
+
+    fn ask_yes_no(&mut self, question: &str) -> bool {
+        self.say(question);
+        matches!(self.read_choice().as_deref(), Some("yes"))
+    }
+}
+
+impl<R: BufRead, W: Write> DecisionSource for ConsoleDecisions<R, W> {
+    fn choose_bet(&mut self, round: &Round) -> Option<i32> {
+        let prefix = Self::seat_prefix(round);
+        // At a shared table a player who can't cover the minimum sits the round out
+        // rather than being asked for a bet they can't place.
+        if !prefix.is_empty() && round.check_bet(round.rules().min_bet).is_err() {
+            self.say(&format!(
+                "{}You can't cover the table minimum and sit this round out.",
+                prefix
+            ));
+            return None;
+        }
+        loop {
+            self.say(&format!(
+                "{}Your current balance: ${}",
+                prefix,
+                round.balance()
+            ));
+            self.say(&format!("{}Enter your bet amount: ", prefix));
+
+            // Parse the input as an integer.
+            match self.read_choice()?.parse::<i32>() {
+                Ok(bet) => match round.check_bet(bet) {
+                    Ok(()) => return Some(bet),
+                    Err(err) => self.say(&format!("{} Try again.", err)),
+                },
+                Err(_) => self.say("Invalid input. Please enter a valid number."),
+            }
+        }
+    }
+
+    fn choose_action(&mut self, round: &Round) -> Action {
+        let choices = describe_choices(&round.legal_actions());
+        // With several seats in play the cards were dealt around the table, so the
+        // hand is shown again before asking.
+        if let (true, Some(hand)) = (round.seats().len() > 1, round.current_hand()) {
+            let cards: Vec<String> = hand.cards().iter().map(|card| card.to_string()).collect();
+            self.say(&format!(
+                "{} to act with {} (total value: {}).",
+                round.seat_label(),
+                cards.join(", "),
+                hand.value().total
+            ));
+        }
+        loop {
+            self.say(&format!("Do you want to {}?", choices));
+
+            let action = match self.read_choice().as_deref() {
+                Some("hit") => Action::Hit,
+                Some("stand") => Action::Stand,
+                Some("double") => Action::Double,
+                Some("split") => Action::Split,
+                Some("surrender") => Action::Surrender,
+                // A closed input stands so the round can still finish.
+                None => return Action::Stand,
+                _ => {
+                    self.say(&format!("Invalid choice. Please enter {}.", choices));
+                    continue;
+                }
+            };
+            if round.legal_actions().contains(&action) {
+                return action;
+            }
+            self.say(&format!("You cannot {} right now.", action));
+        }
+    }
+
+    fn take_insurance(&mut self, round: &Round) -> Option<i32> {
+        let Some(&Action::Insurance(max)) = round.legal_actions().first() else {
+            return None;
+        };
+        let prefix = Self::seat_prefix(round);
+        loop {
+            self.say(&format!(
+                "{}Dealer shows an Ace. Would you like insurance? Enter an amount up to ${} ('yes' for all of it) or 'no'.",
+                prefix, max
+            ));
+            // A closed input declines.
+            let choice = self.read_choice()?;
+            match choice.as_str() {
+                "yes" => return Some(max),
+                "no" | "0" => return None,
+                _ => match choice.trim_start_matches('$').parse::<i32>() {
+                    Ok(amount) if (1..=max).contains(&amount) => return Some(amount),
+                    _ => self.say(&format!(
+                        "Invalid input. Please enter an amount between $1 and ${}, or 'no'.",
+                        max
+                    )),
+                },
+            }
+        }
+    }
+
+    fn take_even_money(&mut self, round: &Round) -> bool {
+        self.ask_yes_no(&format!(
+            "{}Dealer shows an Ace. Would you like even money? (yes/no)",
+            Self::seat_prefix(round)
+        ))
+    }
+
+    fn surrender_early(&mut self, round: &Round) -> bool {
+        self.ask_yes_no(&format!(
+            "{}Would you like to surrender half of your bet before the dealer checks for Blackjack? (yes/no)",
+            Self::seat_prefix(round)
+        ))
+    }
+
+    fn keep_playing(&mut self, _game: &Game, _player: usize) -> bool {
+        self.ask_yes_no("Do you want to play another round? (yes/no)")
+    }
+}
+
+/// Plays back a fixed list of decisions, for tests and replays.
+///
+/// Once a list runs out the player leaves the table, stands, declines insurance and
+/// even money, and stops playing. A `Surrender` next in the actions is also the answer
+/// to early surrender; anything else keeps the hand and stays in the list.
+#[derive(Debug, Default)]
+pub struct ScriptedDecisions {
+    bets: VecDeque<i32>,
+    actions: VecDeque<Action>,
+    insurance: VecDeque<Option<i32>>,
+    even_money: VecDeque<bool>,
+    keep_playing: VecDeque<bool>,
+}
+
+impl ScriptedDecisions {
+    pub fn new() -> ScriptedDecisions {
+        ScriptedDecisions::default()
+    }
+
+    pub fn with_bets(mut self, bets: impl IntoIterator<Item = i32>) -> Self {
+        self.bets.extend(bets);
+        self
+    }
+
+    pub fn with_actions(mut self, actions: impl IntoIterator<Item = Action>) -> Self {
+        self.actions.extend(actions);
+        self
+    }
+
+    pub fn with_insurance(mut self, amounts: impl IntoIterator<Item = Option<i32>>) -> Self {
+        self.insurance.extend(amounts);
+        self
+    }
+
+    pub fn with_even_money(mut self, answers: impl IntoIterator<Item = bool>) -> Self {
+        self.even_money.extend(answers);
+        self
+    }
+
+    pub fn with_keep_playing(mut self, answers: impl IntoIterator<Item = bool>) -> Self {
+        self.keep_playing.extend(answers);
+        self
+    }
+}
+
+impl DecisionSource for ScriptedDecisions {
+    fn choose_bet(&mut self, _round: &Round) -> Option<i32> {
+        self.bets.pop_front()
+    }
+
+    fn choose_action(&mut self, _round: &Round) -> Action {
+        self.actions.pop_front().unwrap_or(Action::Stand)
+    }
+
+    fn take_insurance(&mut self, _round: &Round) -> Option<i32> {
+        self.insurance.pop_front().flatten()
+    }
+
+    fn take_even_money(&mut self, _round: &Round) -> bool {
+        self.even_money.pop_front().unwrap_or(false)
+    }
+
+    fn surrender_early(&mut self, _round: &Round) -> bool {
+        if self.actions.front() == Some(&Action::Surrender) {
+            self.actions.pop_front();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn keep_playing(&mut self, _game: &Game, _player: usize) -> bool {
+        self.keep_playing.pop_front().unwrap_or(false)
+    }
+}
+
+/// A bot that bets a flat amount and plays basic strategy, or another `Strategy`.
+#[derive(Debug, Clone)]
+pub struct StrategyBot {
+    bet: i32,
+    strategy: Strategy,
+}
+
+impl StrategyBot {
+    pub fn new(bet: i32) -> StrategyBot {
+        StrategyBot {
+            bet,
+            strategy: Strategy::Basic,
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+}
+
+impl DecisionSource for StrategyBot {
+    fn choose_bet(&mut self, round: &Round) -> Option<i32> {
+        // Bet what is left once the balance drops below the flat bet, and keep within
+        // the table limits.
+        let rules = round.rules();
+        let max = round.balance().min(rules.max_bet.unwrap_or(i32::MAX));
+        let bet = self.bet.max(rules.min_bet).min(max);
+        if round.check_bet(bet).is_ok() {
+            Some(bet)
+        } else {
+            None
+        }
+    }
+
+    fn choose_action(&mut self, round: &Round) -> Action {
+        match (round.current_hand(), round.dealer().upcard()) {
+            (Some(hand), Some(upcard)) => {
+                self.strategy.choose(hand, upcard, &round.legal_actions())
+            }
+            _ => Action::Stand,
+        }
+    }
+
+    fn keep_playing(&mut self, game: &Game, player: usize) -> bool {
+        game.players()
+            .get(player)
+            .is_some_and(|player| player.balance() >= game.rules().min_bet)
+    }
+}
+
+/// Gives every player at a table their own decision source, so that each seat is
+/// played by whoever sits there: a person at the console or a bot.
+///
+/// Sources are added in player order; every question goes to the source of the player
+/// whose seat is asked.
+#[derive(Default)]
+pub struct TableDecisions {
+    players: Vec<Box<dyn DecisionSource>>,
+}
+
+impl TableDecisions {
+    pub fn new() -> TableDecisions {
+        TableDecisions::default()
+    }
+
+    /// Adds the source for the next player.
+    pub fn with_player(mut self, source: Box<dyn DecisionSource>) -> Self {
+        self.players.push(source);
+        self
+    }
+
+    fn source(&mut self, player: usize) -> &mut dyn DecisionSource {
+        self.players
+            .get_mut(player)
+            .map(|source| source.as_mut())
+            .expect("every player has a decision source")
+    }
+}
+
+impl DecisionSource for TableDecisions {
+    fn choose_bet(&mut self, round: &Round) -> Option<i32> {
+        self.source(round.player_index()).choose_bet(round)
+    }
+
+    fn choose_action(&mut self, round: &Round) -> Action {
+        self.source(round.player_index()).choose_action(round)
+    }
+
+    fn take_insurance(&mut self, round: &Round) -> Option<i32> {
+        self.source(round.player_index()).take_insurance(round)
+    }
+
+    fn take_even_money(&mut self, round: &Round) -> bool {
+        self.source(round.player_index()).take_even_money(round)
+    }
+
+    fn surrender_early(&mut self, round: &Round) -> bool {
+        self.source(round.player_index()).surrender_early(round)
+    }
+
+    fn keep_playing(&mut self, game: &Game, player: usize) -> bool {
+        self.source(player).keep_playing(game, player)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::round::RoundState;
+    use crate::rules::{Rules, Surrender};
+    use crate::settle::Outcome;
+    use crate::shoe;
+    use std::io::Cursor;
+
+    fn stacked_game(balance: i32, cards: &[&str]) -> Game {
+        Game::with_shoe(balance, shoe::stacked(cards))
+    }
+
+    fn console(input: &str) -> ConsoleDecisions<Cursor<Vec<u8>>, Vec<u8>> {
+        ConsoleDecisions::new(Cursor::new(input.as_bytes().to_vec()), Vec::new())
+    }
+
+    #[test]
+    fn test_betting() {
+        let mut game = Game::new(100);
+        let round = game.start_round();
+
+        // Invalid input, a bet less than or equal to 0 and a bet greater than the
+        // player's balance are all refused before a valid bet is accepted.
+        let mut decisions = console("ten\n0\n101\n10\n");
+        assert_eq!(decisions.choose_bet(&round), Some(10));
+
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("Invalid input. Please enter a valid number."));
+        assert!(output.contains("Please enter a positive value."));
+        assert!(output.contains("You cannot bet more than your balance."));
+
+        // A bet that is equivalent to the player's balance is allowed.
+        let mut decisions = console("100\n");
+        assert_eq!(decisions.choose_bet(&round), Some(100));
+
+        // Closing the input leaves the table.
+        let mut decisions = console("");
+        assert_eq!(decisions.choose_bet(&round), None);
+    }
+
+    #[test]
+    fn test_player_turn() {
+        // The player holds 16 against a dealer 9.
+        let mut game = stacked_game(100, &["10H", "9C", "6D", "8S"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(round.state(), RoundState::PlayerTurn(0, 0));
+
+        // An invalid choice is asked again. The prompt lists every legal choice.
+        let mut decisions = console("fold\nHIT\n");
+        assert_eq!(decisions.choose_action(&round), Action::Hit);
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("Do you want to 'hit', 'stand' or 'double'?"));
+        assert!(output.contains("Invalid choice. Please enter 'hit', 'stand' or 'double'."));
+
+        let mut decisions = console("double\n");
+        assert_eq!(decisions.choose_action(&round), Action::Double);
+
+        let mut decisions = console("stand\n");
+        assert_eq!(decisions.choose_action(&round), Action::Stand);
+
+        let mut decisions = console("");
+        assert_eq!(decisions.choose_action(&round), Action::Stand);
+
+        // Once the turn is over nothing but a closed input ends the prompt.
+        round.apply(Action::Stand).unwrap();
+        let mut decisions = console("hit\n");
+        assert_eq!(decisions.choose_action(&round), Action::Stand);
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("You cannot hit right now."));
+    }
+
+    #[test]
+    fn test_console_spots() {
+        // The player plays two spots: 10 and 6 on the first, 7 and 7 on the second,
+        // against a dealer 9.
+        let mut game = stacked_game(100, &["10H", "7C", "9C", "6D", "7S", "8S"]);
+        game.add_seat(0).unwrap();
+        let mut round = game.start_round();
+
+        // Each spot's bet is asked for by name.
+        let mut decisions = console("10\n");
+        assert_eq!(decisions.choose_bet(&round), Some(10));
+        round.apply(Action::Bet(10)).unwrap();
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("Spot 1: Enter your bet amount:"));
+
+        let mut decisions = console("10\n");
+        assert_eq!(decisions.choose_bet(&round), Some(10));
+        round.apply(Action::Bet(10)).unwrap();
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("Spot 2: Enter your bet amount:"));
+
+        // Before each decision the acting spot and its hand are shown.
+        round.apply(Action::Deal).unwrap();
+        let mut decisions = console("stand\n");
+        assert_eq!(decisions.choose_action(&round), Action::Stand);
+        round.apply(Action::Stand).unwrap();
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(
+            output.contains("Spot 1 to act with 10 of Hearts, 6 of Diamonds (total value: 16).")
+        );
+
+        let mut decisions = console("split\n");
+        assert_eq!(decisions.choose_action(&round), Action::Split);
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("Spot 2 to act with 7 of Clubs, 7 of Spades (total value: 14)."));
+        assert!(output.contains("Do you want to 'hit', 'stand', 'double' or 'split'?"));
+    }
+
+    #[test]
+    fn test_console_hot_seat() {
+        // Alice and Bob share the console. Bob can't cover the $5 minimum.
+        let mut game = stacked_game(100, &[]);
+        game.set_rules(Rules {
+            min_bet: 5,
+            ..Rules::default()
+        });
+        let bob = game.add_player("Bob", 3);
+        game.add_seat(bob).unwrap();
+        let mut round = game.start_round();
+
+        let mut decisions = console("10\n");
+        assert_eq!(decisions.choose_bet(&round), Some(10));
+        round.apply(Action::Bet(10)).unwrap();
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("Player: Your current balance: $100"));
+
+        // Bob is passed over rather than asked for a bet they can't place.
+        let mut decisions = console("10\n");
+        assert_eq!(decisions.choose_bet(&round), None);
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert_eq!(
+            output,
+            "Bob: You can't cover the table minimum and sit this round out.\n"
+        );
+    }
+
+    #[test]
+    fn test_console_yes_no() {
+        let game = Game::new(100);
+        assert!(console("yes\n").keep_playing(&game, 0));
+        assert!(!console("no\n").keep_playing(&game, 0));
+        assert!(!console("").keep_playing(&game, 0));
+    }
+
+    #[test]
+    fn test_console_insurance() {
+        let mut game = stacked_game(100, &["10H", "AC", "8D", "7S"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        let mut decisions = console("yes\n");
+        assert_eq!(decisions.take_insurance(&round), Some(10));
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("Enter an amount up to $10"));
+
+        // Any amount up to half the bet can be taken; more than that is asked again.
+        let mut decisions = console("11\nmaybe\n4\n");
+        assert_eq!(decisions.take_insurance(&round), Some(4));
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert_eq!(output.matches("between $1 and $10").count(), 2);
+        assert_eq!(console("no\n").take_insurance(&round), None);
+        assert_eq!(console("0\n").take_insurance(&round), None);
+        assert_eq!(console("").take_insurance(&round), None);
+        drop(round);
+
+        // A natural is offered even money instead.
+        let mut game = stacked_game(100, &["AH", "AC", "KD", "7S"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        let mut decisions = console("no\n");
+        assert!(!decisions.take_even_money(&round));
+        assert_eq!(console("yes\n").take_insurance(&round), None);
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("Would you like even money?"));
+    }
+
+    #[test]
+    fn test_early_surrender() {
+        // Player 16 against a dealer Ace, at a table with early surrender.
+        let early_game = || {
+            let mut game = stacked_game(100, &["10H", "AC", "6D", "7S"]);
+            game.set_rules(Rules {
+                surrender: Surrender::Early,
+                ..Rules::default()
+            });
+            game
+        };
+        let mut game = early_game();
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::EarlySurrender(0)));
+
+        let mut decisions = console("yes\n");
+        assert!(decisions.surrender_early(&round));
+        let output = String::from_utf8(decisions.into_output()).unwrap();
+        assert!(output.contains("surrender half of your bet before the dealer checks"));
+
+        // A script surrenders only if a surrender is next, and otherwise keeps its
+        // actions for the hand.
+        let mut decisions = ScriptedDecisions::new().with_actions([Action::Hit]);
+        assert!(!decisions.surrender_early(&round));
+        assert_eq!(decisions.choose_action(&round), Action::Hit);
+        let mut decisions = ScriptedDecisions::new().with_actions([Action::Surrender]);
+        assert!(decisions.surrender_early(&round));
+
+        // Basic strategy gives up 16 against an Ace.
+        assert!(StrategyBot::new(20).surrender_early(&round));
+        drop(round);
+
+        // A whole round asks before insurance and before the dealer checks.
+        let mut game = early_game();
+        let mut decisions = ScriptedDecisions::new()
+            .with_bets([20])
+            .with_actions([Action::Surrender]);
+        let settlements = game.play_round(&mut decisions).unwrap().unwrap();
+        assert_eq!(settlements[0].outcome, Outcome::Surrender);
+        assert_eq!(game.balance(), 90);
+    }
+
+    #[test]
+    fn test_scripted_decisions() {
+        let mut game = Game::new(100);
+        let mut decisions = ScriptedDecisions::new()
+            .with_bets([10])
+            .with_actions([Action::Hit])
+            .with_keep_playing([true]);
+
+        let round = game.start_round();
+        assert_eq!(decisions.choose_bet(&round), Some(10));
+        assert_eq!(decisions.choose_action(&round), Action::Hit);
+        assert_eq!(decisions.take_insurance(&round), None);
+        assert!(!decisions.take_even_money(&round));
+
+        // Once the script runs out the player stands and leaves.
+        assert_eq!(decisions.choose_action(&round), Action::Stand);
+        assert_eq!(decisions.choose_bet(&round), None);
+        drop(round);
+        assert!(decisions.keep_playing(&game, 0));
+        assert!(!decisions.keep_playing(&game, 0));
+    }
+
+    #[test]
+    fn test_shared_source() {
+        // Alice and Bob answer from the same script, the way players share a console,
+        // while the bot between them plays on its own.
+        let mut game = stacked_game(100, &["10H", "10S", "9D", "7C", "8D", "9S", "9H", "10C"]);
+        let bot = game.add_player("Bot", 100);
+        let bob = game.add_player("Bob", 100);
+        game.add_seat(bot).unwrap();
+        game.add_seat(bob).unwrap();
+        let console = Rc::new(RefCell::new(ScriptedDecisions::new().with_bets([10, 20])));
+        let mut decisions = TableDecisions::new()
+            .with_player(Box::new(console.clone()))
+            .with_player(Box::new(
+                StrategyBot::new(5).with_strategy(Strategy::NeverBust),
+            ))
+            .with_player(Box::new(console.clone()));
+
+        let settlements = game.play_round(&mut decisions).unwrap().unwrap();
+        let wagers: Vec<(usize, i32)> = settlements
+            .iter()
+            .map(|settlement| (settlement.seat, settlement.wager))
+            .collect();
+        assert_eq!(wagers, vec![(0, 10), (1, 5), (2, 20)]);
+    }
+
+    #[test]
+    fn test_strategy_bot() {
+        // The bot starts on 9 against a dealer 10 and draws up to 19.
+        let mut game = stacked_game(15, &["5H", "10C", "4D", "7S", "2H", "3C", "5D"]);
+        let mut bot = StrategyBot::new(25);
+
+        // The bot never bets more than its balance.
+        let mut round = game.start_round();
+        assert_eq!(bot.choose_bet(&round), Some(15));
+        round.apply(Action::Bet(15)).unwrap();
+        round.apply(Action::Deal).unwrap();
+
+        while let RoundState::PlayerTurn(_, _) = round.state() {
+            let action = bot.choose_action(&round);
+            assert!(round.legal_actions().contains(&action));
+            round.apply(action).unwrap();
+        }
+        assert_eq!(round.seat().hands()[0].hand.value().total, 19);
+        drop(round);
+
+        // The bot keeps to the table limits and leaves once it can't cover the minimum.
+        let mut game = Game::new(100);
+        game.set_rules(Rules {
+            min_bet: 5,
+            max_bet: Some(20),
+            ..Rules::default()
+        });
+        let round = game.start_round();
+        assert_eq!(StrategyBot::new(2).choose_bet(&round), Some(5));
+        assert_eq!(StrategyBot::new(50).choose_bet(&round), Some(20));
+        drop(round);
+
+        let mut game = Game::new(4);
+        game.set_rules(Rules {
+            min_bet: 5,
+            ..Rules::default()
+        });
+        assert_eq!(StrategyBot::new(10).choose_bet(&game.start_round()), None);
+        assert!(!StrategyBot::new(10).keep_playing(&game, 0));
+    }
+}
@@ -0,0 +1,594 @@
+use std::cell::RefCell;
+use std::io::{Stdout, Write};
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::card::Card;
+use crate::game::Game;
+use crate::hand::HandValue;
+use crate::settle::Outcome;
+
+/// Whose hand an event is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Participant {
+    // The seat with the given index.
+    Seat(usize),
+    Dealer,
+}
+
+/// Something that happened during a round. Events about a player's bets and hands
+/// carry the index of the seat they happened at.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    // The bet on a seat was accepted.
+    BetPlaced {
+        seat: usize,
+        amount: i32,
+    },
+    // A card was dealt face up.
+    CardDealt {
+        to: Participant,
+        card: Card,
+    },
+    // The dealer's hole card was dealt face down.
+    HoleCardDealt,
+    // The player placed an insurance bet.
+    InsuranceTaken {
+        seat: usize,
+        amount: i32,
+    },
+    // The player took even money on a natural.
+    EvenMoneyTaken {
+        seat: usize,
+    },
+    // The dealer looked under an Ace or ten upcard for a blackjack.
+    DealerPeeks {
+        blackjack: bool,
+    },
+    // A hand's total after it changed.
+    HandTotal {
+        of: Participant,
+        value: HandValue,
+    },
+    // The player split a pair and the seat now has `hands` hands.
+    PlayerSplits {
+        seat: usize,
+        hands: usize,
+    },
+    // Play moved on to the split hand with the given index.
+    PlayingHand {
+        seat: usize,
+        hand: usize,
+    },
+    // The player doubled down; `wager` is the new stake on the hand.
+    PlayerDoubles {
+        seat: usize,
+        wager: i32,
+    },
+    // The player gave up their hand for half of the wager.
+    PlayerSurrenders {
+        seat: usize,
+    },
+    // The hand was finished without busting.
+    PlayerStands {
+        seat: usize,
+        total: i32,
+    },
+    // The hand went over 21.
+    PlayerBusts {
+        seat: usize,
+        total: i32,
+    },
+    // The dealer shows their hand before playing it out.
+    DealerReveals {
+        cards: Vec<Card>,
+        total: i32,
+    },
+    // A wager was settled, with how much the player won or lost on it.
+    Settled {
+        seat: usize,
+        outcome: Outcome,
+        delta: i32,
+    },
+    // A player's balance once their bets are settled, by the player's index. Sent even
+    // when a push leaves it where it was before the bet.
+    BalanceChanged {
+        player: usize,
+        balance: i32,
+    },
+    // The cut card came out and the shoe was shuffled before this round.
+    ShoeShuffled {
+        cards: usize,
+    },
+}
+
+/// Receives every event a game emits.
+pub trait EventSubscriber {
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+// A shared subscriber lets the caller keep a handle on it, for example to read a log
+// or statistics once the game has run.
+impl<T: EventSubscriber> EventSubscriber for Rc<RefCell<T>> {
+    fn on_event(&mut self, event: &GameEvent) {
+        self.borrow_mut().on_event(event);
+    }
+}
+
+fn hand_names(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_value(value: &HandValue) -> String {
+    if value.is_blackjack {
+        format!("{} (Blackjack!)", value.total)
+    } else if value.soft {
+        format!("{} (soft)", value.total)
+    } else {
+        value.total.to_string()
+    }
+}
+
+/// Prints events as text, the way the console game shows them.
+///
+/// Everyone is called "Player" unless the renderer is told who sits where with
+/// `with_table`.
+pub struct ConsoleRenderer<W> {
+    output: W,
+    // The name shown for each seat, and for each player.
+    seats: Vec<String>,
+    players: Vec<String>,
+}
+
+impl ConsoleRenderer<Stdout> {
+    pub fn stdout() -> Self {
+        ConsoleRenderer::new(std::io::stdout())
+    }
+}
+
+impl<W: Write> ConsoleRenderer<W> {
+    pub fn new(output: W) -> Self {
+        ConsoleRenderer {
+            output,
+            seats: Vec::new(),
+            players: Vec::new(),
+        }
+    }
+
+    /// Names every seat after `Game::seat_label`. Balances are named after their
+    /// players once more than one player is seated.
+    pub fn with_table(mut self, game: &Game) -> Self {
+        self.seats = (0..game.seats().len())
+            .map(|seat| game.seat_label(seat))
+            .collect();
+        let first = game.seats().first().map(|seat| seat.player());
+        if game.seats().iter().any(|seat| Some(seat.player()) != first) {
+            self.players = game
+                .players()
+                .iter()
+                .map(|player| player.name().to_string())
+                .collect();
+        }
+        self
+    }
+
+    pub fn into_output(self) -> W {
+        self.output
+    }
+
+    fn seat_name(&self, seat: usize) -> &str {
+        self.seats.get(seat).map_or("Player", String::as_str)
+    }
+}
+
+impl<W: Write> EventSubscriber for ConsoleRenderer<W> {
+    fn on_event(&mut self, event: &GameEvent) {
+        let text = match event {
+            GameEvent::BetPlaced { seat, amount } => {
+                if self.seats.is_empty() {
+                    format!("You placed a bet of ${}", amount)
+                } else {
+                    format!("{} places a bet of ${}", self.seat_name(*seat), amount)
+                }
+            }
+            GameEvent::CardDealt {
+                to: Participant::Seat(seat),
+                card,
+            } => format!("{} is dealt the {}", self.seat_name(*seat), card),
+            GameEvent::CardDealt {
+                to: Participant::Dealer,
+                card,
+            } => format!("Dealer is dealt the {}", card),
+            GameEvent::HoleCardDealt => "Dealer is dealt a card face down".to_string(),
+            GameEvent::InsuranceTaken { seat, amount } => {
+                format!("{} takes insurance for ${}.", self.seat_name(*seat), amount)
+            }
+            GameEvent::EvenMoneyTaken { seat } => {
+                format!("{} takes even money.", self.seat_name(*seat))
+            }
+            GameEvent::DealerPeeks { blackjack: true } => {
+                "Dealer checks the hole card... Dealer has Blackjack!".to_string()
+            }
+            GameEvent::DealerPeeks { blackjack: false } => {
+                "Dealer checks the hole card... no Blackjack.".to_string()
+            }
+            GameEvent::HandTotal {
+                of: Participant::Seat(seat),
+                value,
+            } => {
+                if self.seats.is_empty() {
+                    format!("Total value: {}", describe_value(value))
+                } else {
+                    format!(
+                        "{}'s total value: {}",
+                        self.seat_name(*seat),
+                        describe_value(value)
+                    )
+                }
+            }
+            GameEvent::HandTotal {
+                of: Participant::Dealer,
+                value,
+            } => format!("Dealer's total value: {}", describe_value(value)),
+            GameEvent::PlayerSplits { seat, hands } => format!(
+                "{} splits the pair and now plays {} hands.",
+                self.seat_name(*seat),
+                hands
+            ),
+            GameEvent::PlayingHand { hand, .. } => format!("Playing hand {}.", hand + 1),
+            GameEvent::PlayerDoubles { seat, wager } => format!(
+                "{} doubles down. The bet is now ${}.",
+                self.seat_name(*seat),
+                wager
+            ),
+            GameEvent::PlayerSurrenders { seat } => {
+                format!("{} surrenders.", self.seat_name(*seat))
+            }
+            GameEvent::PlayerStands { seat, .. } => format!("{} stands.", self.seat_name(*seat)),
+            GameEvent::PlayerBusts { seat, .. } => format!("{} busts!", self.seat_name(*seat)),
+            GameEvent::DealerReveals { cards, total } => {
+                format!(
+                    "Dealer's hand: {} (total value: {})",
+                    hand_names(cards),
+                    total
+                )
+            }
+            GameEvent::Settled {
+                seat,
+                outcome,
+                delta,
+            } => {
+                let name = self.seat_name(*seat);
+                match outcome {
+                    Outcome::Win => format!("{} wins ${}!", name, delta),
+                    Outcome::Blackjack => format!("Blackjack! {} wins ${}!", name, delta),
+                    Outcome::Push if self.seats.is_empty() => {
+                        "It's a tie. The bet is returned to the player.".to_string()
+                    }
+                    Outcome::Push => format!("It's a tie. The bet is returned to {}.", name),
+                    Outcome::Lose => format!("Dealer wins. {} loses ${}!", name, -delta),
+                    Outcome::Surrender => {
+                        format!("Half of the bet is returned. {} loses ${}.", name, -delta)
+                    }
+                    Outcome::InsuranceWin => format!("Insurance pays ${}!", delta),
+                    Outcome::InsuranceLose => format!("Insurance loses ${}.", -delta),
+                }
+            }
+            GameEvent::BalanceChanged { player, balance } => match self.players.get(*player) {
+                Some(name) => format!("{}'s balance: ${}", name, balance),
+                None => format!("Current balance: ${}", balance),
+            },
+            GameEvent::ShoeShuffled { .. } => {
+                "The cut card is out. Shuffling the shoe.".to_string()
+            }
+        };
+        writeln!(self.output, "{}", text).expect("Failed to write output");
+    }
+}
+
+/// Records every event, e.g. for a hand history.
+#[derive(Debug, Default)]
+pub struct EventLog {
+    events: Vec<GameEvent>,
+}
+
+impl EventLog {
+    pub fn new() -> EventLog {
+        EventLog::default()
+    }
+
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
+}
+
+impl EventSubscriber for EventLog {
+    fn on_event(&mut self, event: &GameEvent) {
+        self.events.push(event.clone());
+    }
+}
+
+/// Counts results over a session. Insurance bets count towards `net` only.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionStats {
+    // Hands settled.
+    pub rounds: u32,
+    // Hands won, including blackjacks.
+    pub wins: u32,
+    pub blackjacks: u32,
+    pub losses: u32,
+    pub pushes: u32,
+    pub busts: u32,
+    pub surrenders: u32,
+    pub net: i32,
+}
+
+impl SessionStats {
+    pub fn new() -> SessionStats {
+        SessionStats::default()
+    }
+}
+
+impl EventSubscriber for SessionStats {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::PlayerBusts { .. } => self.busts += 1,
+            GameEvent::Settled { outcome, delta, .. } => {
+                self.net += delta;
+                match outcome {
+                    Outcome::InsuranceWin | Outcome::InsuranceLose => return,
+                    Outcome::Win => self.wins += 1,
+                    Outcome::Blackjack => {
+                        self.wins += 1;
+                        self.blackjacks += 1;
+                    }
+                    Outcome::Lose => self.losses += 1,
+                    Outcome::Surrender => self.surrenders += 1,
+                    Outcome::Push => self.pushes += 1,
+                }
+                self.rounds += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Counts results over a session separately for each player at a table, adding up
+/// every seat they play.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PlayerStats {
+    // The player on each seat, by seat index.
+    seats: Vec<usize>,
+    // The stats of each player, by player index.
+    players: Vec<SessionStats>,
+}
+
+impl PlayerStats {
+    /// Starts counting for the players seated at the game.
+    pub fn new(game: &Game) -> PlayerStats {
+        PlayerStats {
+            seats: game.seats().iter().map(|seat| seat.player()).collect(),
+            players: vec![SessionStats::new(); game.players().len()],
+        }
+    }
+
+    /// The stats of the player with the given index.
+    pub fn get(&self, player: usize) -> Option<&SessionStats> {
+        self.players.get(player)
+    }
+}
+
+impl EventSubscriber for PlayerStats {
+    fn on_event(&mut self, event: &GameEvent) {
+        let seat = match event {
+            GameEvent::Settled { seat, .. } | GameEvent::PlayerBusts { seat, .. } => *seat,
+            _ => return,
+        };
+        if let Some(stats) = self
+            .seats
+            .get(seat)
+            .and_then(|&player| self.players.get_mut(player))
+        {
+            stats.on_event(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::{Rank, Suit};
+    use crate::shoe::Shoe;
+
+    fn ace() -> Card {
+        Card::new(Rank::Ace, Suit::Hearts)
+    }
+
+    #[test]
+    fn test_console_renderer() {
+        let mut renderer = ConsoleRenderer::new(Vec::new());
+        renderer.on_event(&GameEvent::BetPlaced {
+            seat: 0,
+            amount: 10,
+        });
+        renderer.on_event(&GameEvent::CardDealt {
+            to: Participant::Seat(0),
+            card: ace(),
+        });
+        renderer.on_event(&GameEvent::DealerReveals {
+            cards: vec![ace()],
+            total: 11,
+        });
+        renderer.on_event(&GameEvent::Settled {
+            seat: 0,
+            outcome: Outcome::Lose,
+            delta: -10,
+        });
+        renderer.on_event(&GameEvent::BalanceChanged {
+            player: 0,
+            balance: 90,
+        });
+
+        let output = String::from_utf8(renderer.into_output()).unwrap();
+        assert_eq!(
+            output,
+            "You placed a bet of $10\n\
+             Player is dealt the Ace of Hearts\n\
+             Dealer's hand: Ace of Hearts (total value: 11)\n\
+             Dealer wins. Player loses $10!\n\
+             Current balance: $90\n"
+        );
+    }
+
+    #[test]
+    fn test_console_renderer_table() {
+        let mut game = Game::table(crate::shoe::Shoe::new(1, 0.75));
+        let alice = game.add_player("Alice", 100);
+        let bob = game.add_player("Bob", 100);
+        game.add_seat(alice).unwrap();
+        game.add_seat(bob).unwrap();
+
+        // At a table every line names the seat's player.
+        let mut renderer = ConsoleRenderer::new(Vec::new()).with_table(&game);
+        renderer.on_event(&GameEvent::BetPlaced {
+            seat: 1,
+            amount: 10,
+        });
+        renderer.on_event(&GameEvent::CardDealt {
+            to: Participant::Seat(0),
+            card: ace(),
+        });
+        renderer.on_event(&GameEvent::PlayerStands { seat: 1, total: 20 });
+        renderer.on_event(&GameEvent::Settled {
+            seat: 0,
+            outcome: Outcome::Push,
+            delta: 0,
+        });
+        renderer.on_event(&GameEvent::BalanceChanged {
+            player: 1,
+            balance: 110,
+        });
+
+        let output = String::from_utf8(renderer.into_output()).unwrap();
+        assert_eq!(
+            output,
+            "Bob places a bet of $10\n\
+             Alice is dealt the Ace of Hearts\n\
+             Bob stands.\n\
+             It's a tie. The bet is returned to Alice.\n\
+             Bob's balance: $110\n"
+        );
+    }
+
+    #[test]
+    fn test_session_stats() {
+        let mut stats = SessionStats::new();
+        stats.on_event(&GameEvent::PlayerBusts { seat: 0, total: 25 });
+        stats.on_event(&GameEvent::Settled {
+            seat: 0,
+            outcome: Outcome::Lose,
+            delta: -10,
+        });
+        stats.on_event(&GameEvent::Settled {
+            seat: 0,
+            outcome: Outcome::Win,
+            delta: 10,
+        });
+        stats.on_event(&GameEvent::Settled {
+            seat: 0,
+            outcome: Outcome::Push,
+            delta: 0,
+        });
+        stats.on_event(&GameEvent::Settled {
+            seat: 0,
+            outcome: Outcome::Blackjack,
+            delta: 15,
+        });
+        stats.on_event(&GameEvent::Settled {
+            seat: 0,
+            outcome: Outcome::Surrender,
+            delta: -5,
+        });
+        // Insurance moves the money but is not a hand of its own.
+        stats.on_event(&GameEvent::Settled {
+            seat: 0,
+            outcome: Outcome::InsuranceLose,
+            delta: -5,
+        });
+        assert_eq!(
+            stats,
+            SessionStats {
+                rounds: 5,
+                wins: 2,
+                blackjacks: 1,
+                losses: 1,
+                pushes: 1,
+                busts: 1,
+                surrenders: 1,
+                net: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_player_stats() {
+        // Alice plays seats 0 and 2, Bob seat 1.
+        let mut game = Game::table(Shoe::default());
+        let alice = game.add_player("Alice", 100);
+        let bob = game.add_player("Bob", 100);
+        for player in [alice, bob, alice] {
+            game.add_seat(player).unwrap();
+        }
+        let mut stats = PlayerStats::new(&game);
+        for (seat, outcome, delta) in [
+            (0, Outcome::Win, 10),
+            (1, Outcome::Lose, -10),
+            (2, Outcome::Push, 0),
+        ] {
+            stats.on_event(&GameEvent::Settled {
+                seat,
+                outcome,
+                delta,
+            });
+        }
+        stats.on_event(&GameEvent::PlayerBusts { seat: 1, total: 24 });
+        assert_eq!(
+            stats.get(alice),
+            Some(&SessionStats {
+                rounds: 2,
+                wins: 1,
+                pushes: 1,
+                net: 10,
+                ..SessionStats::default()
+            })
+        );
+        assert_eq!(
+            stats.get(bob),
+            Some(&SessionStats {
+                rounds: 1,
+                losses: 1,
+                busts: 1,
+                net: -10,
+                ..SessionStats::default()
+            })
+        );
+        assert_eq!(stats.get(2), None);
+    }
+
+    #[test]
+    fn test_shared_subscriber() {
+        let log = Rc::new(RefCell::new(EventLog::new()));
+        let mut subscriber: Box<dyn EventSubscriber> = Box::new(log.clone());
+        subscriber.on_event(&GameEvent::BetPlaced { seat: 0, amount: 5 });
+        assert_eq!(
+            log.borrow().events(),
+            &[GameEvent::BetPlaced { seat: 0, amount: 5 }]
+        );
+    }
+}
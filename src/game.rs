@@ -0,0 +1,541 @@
+use std::fmt;
+
+use crate::bankroll::{Bankroll, BetError};
+use crate::dealer::DealerHand;
+use crate::decision::DecisionSource;
+use crate::event::{EventSubscriber, GameEvent};
+use crate::player::Player;
+use crate::round::{Action, ActionError, Round, RoundState};
+use crate::rules::Rules;
+use crate::seat::Seat;
+use crate::settle::Settlement;
+use crate::shoe::Shoe;
+
+/// The most seats a table has.
+pub const MAX_SEATS: usize = 7;
+
+/// Why a seat could not be added to the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeatError {
+    // Every one of the table's seats is taken.
+    TableFull,
+    // There is no player with the given index.
+    UnknownPlayer(usize),
+}
+
+impl fmt::Display for SeatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeatError::TableFull => write!(f, "the table is full ({} seats)", MAX_SEATS),
+            SeatError::UnknownPlayer(player) => write!(f, "there is no player {}", player),
+        }
+    }
+}
+
+impl std::error::Error for SeatError {}
+
+/// A blackjack session at a table of up to seven seats against one dealer.
+///
+/// Every seat is played by one of the game's players, and a player may play several.
+/// The simple constructors seat a single player, called "Player"; `table` starts with
+/// no one seated.
+pub struct Game {
+    pub(crate) players: Vec<Player>,
+    pub(crate) seats: Vec<Seat>,
+    pub(crate) dealer: DealerHand,
+    pub(crate) shoe: Shoe,
+    pub(crate) rules: Rules,
+    subscribers: Vec<Box<dyn EventSubscriber>>,
+}
+
+impl Game {
+    /// Creates a game where the player starts with the given balance, played under the
+    /// default rules from a six-deck shoe.
+    pub fn new(balance: i32) -> Game {
+        Game::with_rules(balance, Rules::default())
+    }
+
+    /// Creates a game played under the given rules, dealt from a shoe with the rules'
+    /// number of decks and penetration.
+    pub fn with_rules(balance: i32, rules: Rules) -> Game {
+        let mut game = Game::with_shoe(balance, Shoe::new(rules.decks, rules.penetration()));
+        game.set_rules(rules);
+        game
+    }
+
+    /// Creates a game dealt from the given shoe. The shoe is kept from round to round
+    /// and only shuffled once the cut card comes out.
+    pub fn with_shoe(balance: i32, shoe: Shoe) -> Game {
+        let mut game = Game::table(shoe);
+        let player = game.add_player("Player", balance);
+        game.add_seat(player)
+            .expect("an empty table has free seats");
+        game
+    }
+
+    /// Creates an empty table dealt from the given shoe. Players and their seats are
+    /// added with `add_player` and `add_seat`.
+    pub fn table(shoe: Shoe) -> Game {
+        Game {
+            players: Vec::new(),
+            seats: Vec::new(),
+            dealer: DealerHand::new(),
+            shoe,
+            rules: Rules::default(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Adds a player with their own bankroll and returns their index. The player takes
+    /// part once they are given a seat.
+    pub fn add_player(&mut self, name: impl Into<String>, balance: i32) -> usize {
+        self.players.push(Player::new(name, balance));
+        self.players.len() - 1
+    }
+
+    /// Seats a player at the next free seat and returns its index. Seats are dealt and
+    /// play in the order they were added.
+    pub fn add_seat(&mut self, player: usize) -> Result<usize, SeatError> {
+        if player >= self.players.len() {
+            return Err(SeatError::UnknownPlayer(player));
+        }
+        if self.seats.len() >= MAX_SEATS {
+            return Err(SeatError::TableFull);
+        }
+        self.seats.push(Seat::new(player));
+        Ok(self.seats.len() - 1)
+    }
+
+    /// Sets the table rules for the rounds that follow. When they change the number of
+    /// decks or the penetration, the shoe is rebuilt to match unless it already does,
+    /// and the last round's cards go back into it.
+    pub fn set_rules(&mut self, rules: Rules) {
+        let shoe_changed = rules.decks != self.rules.decks
+            || rules.penetration_percent != self.rules.penetration_percent;
+        let shoe_matches =
+            self.shoe.decks() == rules.decks && self.shoe.penetration() == rules.penetration();
+        if shoe_changed && !shoe_matches {
+            // The fresh shoe holds every card, so none can be left on the table.
+            for seat in self.seats.iter_mut() {
+                seat.clear();
+            }
+            self.dealer.clear();
+            self.shoe.rebuild(rules.decks, rules.penetration());
+        }
+        self.rules = rules;
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Adds a subscriber that receives every event from now on.
+    pub fn subscribe(&mut self, subscriber: Box<dyn EventSubscriber>) {
+        self.subscribers.push(subscriber);
+    }
+
+    pub(crate) fn emit(&mut self, event: GameEvent) {
+        for subscriber in self.subscribers.iter_mut() {
+            subscriber.on_event(&event);
+        }
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    /// The name a seat goes by: its player's name, or the spot's number when the
+    /// player plays several, e.g. "Alice's spot 2", or just "Spot 2" when no one else
+    /// is seated.
+    pub fn seat_label(&self, seat: usize) -> String {
+        let player = self.seats[seat].player();
+        let name = self.players[player].name();
+        let spots: Vec<usize> = (0..self.seats.len())
+            .filter(|&spot| self.seats[spot].player() == player)
+            .collect();
+        if spots.len() == 1 {
+            return name.to_string();
+        }
+        let spot = spots.iter().position(|&spot| spot == seat).unwrap_or(0) + 1;
+        if spots.len() == self.seats.len() {
+            format!("Spot {}", spot)
+        } else {
+            format!("{}'s spot {}", name, spot)
+        }
+    }
+
+    /// The first player's bankroll: the only one at a single-player table.
+    pub fn bankroll(&self) -> &Bankroll {
+        &self.players[0].bankroll
+    }
+
+    /// The first seat: the only one at a single-player table.
+    pub fn seat(&self) -> &Seat {
+        &self.seats[0]
+    }
+
+    pub fn dealer(&self) -> &DealerHand {
+        &self.dealer
+    }
+
+    pub fn shoe(&self) -> &Shoe {
+        &self.shoe
+    }
+
+    /// The first player's balance: the only one at a single-player table.
+    pub fn balance(&self) -> i32 {
+        self.players.first().map_or(0, Player::balance)
+    }
+
+    /// Checks that the first player is allowed to place the given bet, both by their
+    /// balance and by the table limits.
+    pub fn check_bet(&self, bet: i32) -> Result<(), BetError> {
+        self.check_player_bet(0, bet)
+    }
+
+    /// Checks that a player is allowed to place the given bet, both by their balance and
+    /// by the table limits.
+    pub fn check_player_bet(&self, player: usize, bet: i32) -> Result<(), BetError> {
+        self.players[player].bankroll.check_bet(bet)?;
+        self.rules.check_bet(bet)
+    }
+
+    /// Starts a new round. The round begins in the betting state and borrows the game
+    /// until it is dropped.
+    pub fn start_round(&mut self) -> Round<'_> {
+        Round::new(self)
+    }
+
+    /// Plays a whole round, asking the decision source for every seat's bet and every
+    /// choice, and returns the settlement of every hand, seat by seat.
+    ///
+    /// A seat whose bet comes back as `None` sits the round out. Returns `None` if every
+    /// seat sits out, which at a single-player table means the player left.
+    pub fn play_round(
+        &mut self,
+        decisions: &mut dyn DecisionSource,
+    ) -> Result<Option<Vec<Settlement>>, ActionError> {
+        let mut round = self.start_round();
+        loop {
+            let action = match round.state() {
+                RoundState::Betting(_) => match decisions.choose_bet(&round) {
+                    Some(bet) => Action::Bet(bet),
+                    None => Action::SitOut,
+                },
+                RoundState::Dealing => Action::Deal,
+                RoundState::EarlySurrender(_) => {
+                    if decisions.surrender_early(&round) {
+                        Action::Surrender
+                    } else {
+                        Action::DeclineSurrender
+                    }
+                }
+                // A natural is offered even money instead of insurance.
+                RoundState::Insurance(_) => match round.legal_actions()[0] {
+                    Action::EvenMoney if decisions.take_even_money(&round) => Action::EvenMoney,
+                    Action::Insurance(_) => match decisions.take_insurance(&round) {
+                        Some(amount) => Action::Insurance(amount),
+                        None => Action::DeclineInsurance,
+                    },
+                    _ => Action::DeclineInsurance,
+                },
+                RoundState::PlayerTurn(_, _) => decisions.choose_action(&round),
+                RoundState::DealerTurn => Action::PlayDealer,
+                RoundState::Settled if round.seats().iter().any(Seat::in_play) => {
+                    return Ok(Some(round.settlements().to_vec()));
+                }
+                RoundState::Settled => return Ok(None),
+            };
+            round.apply(action)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decision::{ScriptedDecisions, StrategyBot, TableDecisions};
+    use crate::event::EventLog;
+    use crate::settle::Outcome;
+    use crate::shoe;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_play_round() {
+        let mut game = Game::with_shoe(100, shoe::stacked(&["10H", "9C", "8D", "7S", "10C"]));
+        let mut decisions = ScriptedDecisions::new().with_bets([10]);
+
+        // The scripted player stands on 18 and the dealer busts drawing to 16.
+        let settlements = game.play_round(&mut decisions).unwrap();
+        assert_eq!(
+            settlements,
+            Some(vec![Settlement {
+                outcome: Outcome::Win,
+                wager: 10,
+                delta: 10,
+                seat: 0,
+            }])
+        );
+        assert_eq!(game.seat().hand(0).unwrap().hand.len(), 2);
+        assert_eq!(game.dealer().value().total, 26);
+        assert_eq!(game.balance(), 110);
+
+        // With no bets left the player leaves the table.
+        assert_eq!(game.play_round(&mut decisions), Ok(None));
+    }
+
+    #[test]
+    fn test_play_round_rejects_illegal_choices() {
+        let mut game = Game::with_shoe(100, shoe::stacked(&["10H", "9C", "6D", "8S"]));
+        let mut decisions = ScriptedDecisions::new().with_bets([500]);
+        assert_eq!(
+            game.play_round(&mut decisions),
+            Err(ActionError::InvalidBet(BetError::ExceedsBalance))
+        );
+
+        let mut decisions = ScriptedDecisions::new()
+            .with_bets([10])
+            .with_actions([Action::Deal]);
+        let result = game.play_round(&mut decisions);
+        assert!(matches!(result, Err(ActionError::Illegal { .. })));
+
+        // The bet of the abandoned round is handed back.
+        assert_eq!(game.balance(), 100);
+    }
+
+    #[test]
+    fn test_with_rules() {
+        let game = Game::with_rules(100, Rules::atlantic_city());
+        assert_eq!(game.shoe().decks(), 8);
+        assert_eq!(game.shoe().remaining(), 8 * 52);
+        assert_eq!(game.rules(), &Rules::atlantic_city());
+
+        // Bets are checked against the table limits as well as the balance.
+        let game = Game::with_rules(
+            100,
+            Rules {
+                min_bet: 25,
+                ..Rules::default()
+            },
+        );
+        assert_eq!(game.check_bet(10), Err(BetError::BelowMinimum(25)));
+        assert_eq!(game.check_bet(200), Err(BetError::ExceedsBalance));
+    }
+
+    #[test]
+    fn test_set_rules() {
+        let mut game = Game::new(100);
+        game.shoe.draw();
+
+        // Rules that leave the shoe alone keep it as it is.
+        game.set_rules(Rules {
+            dealer_hits_soft_17: true,
+            ..Rules::default()
+        });
+        assert_eq!(game.shoe().remaining(), 6 * 52 - 1);
+
+        // A different number of decks or penetration brings a fresh shoe.
+        game.set_rules(Rules::atlantic_city());
+        assert_eq!(game.shoe().decks(), 8);
+        assert_eq!(game.shoe().remaining(), 8 * 52);
+        game.set_rules(Rules {
+            penetration_percent: 50,
+            ..Rules::atlantic_city()
+        });
+        assert_eq!(game.shoe().penetration(), 0.5);
+
+        // A shoe built for the rules already is not shuffled again.
+        let mut game = Game::table(Shoe::seeded(2, 0.75, 7));
+        game.shoe.draw();
+        game.set_rules(Rules {
+            decks: 2,
+            ..Rules::default()
+        });
+        assert_eq!(game.shoe().remaining(), 2 * 52 - 1);
+
+        // The cards of the last round are part of the new shoe, not added to it.
+        let mut game = Game::new(100);
+        game.play_round(&mut StrategyBot::new(10)).unwrap();
+        game.set_rules(Rules {
+            decks: 2,
+            ..Rules::default()
+        });
+        assert!(game.seat().hands().is_empty());
+        assert_eq!(game.dealer().len(), 0);
+        game.start_round();
+        assert_eq!(game.shoe().remaining() + game.shoe().discarded(), 2 * 52);
+    }
+
+    #[test]
+    fn test_play_round_insurance() {
+        // The player insures 18 against a dealer Ace and the dealer has a blackjack.
+        let mut game = Game::with_shoe(100, shoe::stacked(&["10H", "AC", "8D", "KS"]));
+        let mut decisions = ScriptedDecisions::new()
+            .with_bets([20])
+            .with_insurance([Some(10)]);
+        let settlements = game.play_round(&mut decisions).unwrap().unwrap();
+        let outcomes: Vec<Outcome> = settlements.iter().map(|s| s.outcome).collect();
+        assert_eq!(outcomes, vec![Outcome::Lose, Outcome::InsuranceWin]);
+        assert_eq!(game.balance(), 100);
+
+        // Insuring part of the bet covers part of the loss.
+        let mut game = Game::with_shoe(100, shoe::stacked(&["10H", "AC", "8D", "KS"]));
+        let mut decisions = ScriptedDecisions::new()
+            .with_bets([20])
+            .with_insurance([Some(4)]);
+        game.play_round(&mut decisions).unwrap();
+        assert_eq!(game.balance(), 88);
+
+        // A bot that doesn't answer declines.
+        let mut game = Game::with_shoe(100, shoe::stacked(&["10H", "AC", "8D", "KS"]));
+        let mut bot = StrategyBot::new(20);
+        game.play_round(&mut bot).unwrap();
+        assert_eq!(game.balance(), 80);
+    }
+
+    #[test]
+    fn test_shoe_carries_over_between_rounds() {
+        let log = Rc::new(RefCell::new(EventLog::new()));
+        let mut game = Game::with_shoe(1000, Shoe::new(1, 0.5));
+        game.subscribe(Box::new(log.clone()));
+        let mut bot = StrategyBot::new(10);
+
+        for _ in 0..30 {
+            game.play_round(&mut bot).unwrap();
+
+            // No card is lost or added between the shoe, the tray and the table.
+            let in_play: usize = game
+                .seat()
+                .hands()
+                .iter()
+                .map(|seat_hand| seat_hand.hand.len())
+                .sum::<usize>()
+                + game.dealer().len();
+            assert_eq!(
+                game.shoe().remaining() + game.shoe().discarded() + in_play,
+                52
+            );
+        }
+
+        // A single deck cut in half runs out of cards many times over 30 rounds.
+        let shuffles = log
+            .borrow()
+            .events()
+            .iter()
+            .filter(|event| matches!(event, GameEvent::ShoeShuffled { cards: 52 }))
+            .count();
+        assert!(shuffles > 0);
+    }
+
+    #[test]
+    fn test_seeded_games_replay() {
+        let play = |seed: u64| {
+            let log = Rc::new(RefCell::new(EventLog::new()));
+            let mut game = Game::with_shoe(100, Shoe::seeded(1, 0.75, seed));
+            game.subscribe(Box::new(log.clone()));
+            let mut bot = StrategyBot::new(10);
+            for _ in 0..10 {
+                game.play_round(&mut bot).unwrap();
+            }
+            let events = log.borrow().events().to_vec();
+            events
+        };
+
+        // The same seed replays the whole session event for event.
+        assert_eq!(play(42), play(42));
+        assert_ne!(play(42), play(43));
+    }
+
+    #[test]
+    fn test_strategy_bot_session() {
+        let mut game = Game::new(100);
+        let mut bot = StrategyBot::new(10);
+        for _ in 0..20 {
+            if !bot.keep_playing(&game, 0) {
+                break;
+            }
+            assert!(game.play_round(&mut bot).unwrap().is_some());
+        }
+        assert!(game.balance() >= 0);
+    }
+
+    #[test]
+    fn test_add_seat() {
+        let mut game = Game::table(Shoe::new(6, 0.75));
+        assert_eq!(game.add_seat(0), Err(SeatError::UnknownPlayer(0)));
+
+        let alice = game.add_player("Alice", 100);
+        let bob = game.add_player("Bob", 200);
+        assert_eq!((alice, bob), (0, 1));
+        assert_eq!(game.add_seat(bob), Ok(0));
+        for seat in 1..MAX_SEATS {
+            assert_eq!(game.add_seat(alice), Ok(seat));
+        }
+        assert_eq!(game.add_seat(bob), Err(SeatError::TableFull));
+        assert_eq!(game.seats().len(), 7);
+        assert_eq!(game.seats()[0].player(), bob);
+        assert_eq!(game.players()[bob].balance(), 200);
+    }
+
+    #[test]
+    fn test_play_round_table() {
+        // Alice, on seat 0, is scripted to hit her 11 and stand; the bot on seat 1
+        // stands on 19. The dealer holds 17.
+        let mut game = Game::table(shoe::stacked(&["6H", "10S", "7D", "5C", "9C", "10D", "9H"]));
+        let alice = game.add_player("Alice", 100);
+        let bot = game.add_player("Bot", 100);
+        game.add_seat(alice).unwrap();
+        game.add_seat(bot).unwrap();
+        let mut decisions = TableDecisions::new()
+            .with_player(Box::new(
+                ScriptedDecisions::new()
+                    .with_bets([10])
+                    .with_actions([Action::Hit, Action::Stand]),
+            ))
+            .with_player(Box::new(StrategyBot::new(25)));
+
+        let settlements = game.play_round(&mut decisions).unwrap().unwrap();
+        let results: Vec<(usize, Outcome)> = settlements
+            .iter()
+            .map(|settlement| (settlement.seat, settlement.outcome))
+            .collect();
+        assert_eq!(results, vec![(0, Outcome::Win), (1, Outcome::Win)]);
+        assert_eq!(game.players()[alice].balance(), 110);
+        assert_eq!(game.players()[bot].balance(), 125);
+        assert!(decisions.keep_playing(&game, bot));
+
+        // Once Alice's script runs out she sits out and the bot plays alone.
+        let settlements = game.play_round(&mut decisions).unwrap().unwrap();
+        assert!(settlements.iter().all(|settlement| settlement.seat == 1));
+        assert!(!game.seats()[alice].in_play());
+    }
+
+    #[test]
+    fn test_seat_label() {
+        // A lone player on several spots sees them numbered.
+        let mut game = Game::new(100);
+        assert_eq!(game.seat_label(0), "Player");
+        game.add_seat(0).unwrap();
+        assert_eq!(game.seat_label(0), "Spot 1");
+        assert_eq!(game.seat_label(1), "Spot 2");
+
+        // At a shared table the spots carry the player's name.
+        let bob = game.add_player("Bob", 100);
+        game.add_seat(bob).unwrap();
+        game.add_seat(0).unwrap();
+        // (seat, label)
+        for (seat, label) in [
+            (0, "Player's spot 1"),
+            (1, "Player's spot 2"),
+            (2, "Bob"),
+            (3, "Player's spot 3"),
+        ] {
+            assert_eq!(game.seat_label(seat), label);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use crate::card::{Card, Rank};
+
+/// What a hand is worth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandValue {
+    // The best total that does not bust, or the lowest total if every count busts.
+    pub total: i32,
+    // Whether an Ace is being counted as 11, so one more card cannot bust the hand.
+    pub soft: bool,
+    // An Ace and a ten-value card as the first two cards.
+    pub is_blackjack: bool,
+    // Whether the total is over 21.
+    pub is_bust: bool,
+}
+
+/// The cards held in a single hand.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Hand {
+    cards: Vec<Card>,
+}
+
+impl Hand {
+    pub fn new() -> Hand {
+        Hand::default()
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn push(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    /// Takes the last card off the hand.
+    pub fn pop(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    pub fn clear(&mut self) {
+        self.cards.clear();
+    }
+
+    /// Whether the hand is two cards of the same value, e.g. a pair of eights or a King
+    /// and a Ten.
+    pub fn is_pair(&self) -> bool {
+        match self.cards.as_slice() {
+            [first, second] => first.value() == second.value(),
+            _ => false,
+        }
+    }
+
+    /// Calculates the value of the hand, counting as many Aces as 1 as needed.
+    pub fn value(&self) -> HandValue {
+        // Count every Ace as 1 first.
+        let mut total = 0;
+        let mut has_ace = false;
+        for card in self.cards.iter() {
+            if card.rank == Rank::Ace {
+                has_ace = true;
+                total += 1;
+            } else {
+                total += card.value();
+            }
+        }
+
+        // At most one Ace can count as 11 without going over 21.
+        let soft = has_ace && total + 10 <= 21;
+        if soft {
+            total += 10;
+        }
+
+        HandValue {
+            total,
+            soft,
+            is_blackjack: self.cards.len() == 2 && total == 21,
+            is_bust: total > 21,
+        }
+    }
+}
+
+impl From<Vec<Card>> for Hand {
+    fn from(cards: Vec<Card>) -> Hand {
+        Hand { cards }
+    }
+}
+
+/// Deals a card from the deck to a hand.
+pub fn deal_card(hand: &mut Hand, deck: &mut Vec<Card>) {
+    //Because the dec has been shuffled, we can pop the last card from the deck and add it to the hand instead of drawing a card randomly
+    if let Some(card) = deck.pop() {
+        hand.push(card);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::{create_shuffled_deck, Suit};
+    use rand::thread_rng;
+
+    fn hand(ranks: &[Rank]) -> Hand {
+        Hand::from(
+            ranks
+                .iter()
+                .map(|&rank| Card::new(rank, Suit::Spades))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_deal_card() {
+        let mut deck = create_shuffled_deck(&mut thread_rng());
+        let mut hand = Hand::new();
+
+        // Deal a card to the hand.
+        deal_card(&mut hand, &mut deck);
+
+        // Ensure that the hand now contains one card.
+        assert_eq!(hand.len(), 1);
+
+        // Ensure that the card is removed from the deck.
+        assert_eq!(deck.len(), 52 - 1);
+
+        // Deal another card to the hand.
+        deal_card(&mut hand, &mut deck);
+
+        // Ensure that the hand now contains two cards.
+        assert_eq!(hand.len(), 2);
+
+        // Ensure that the card is removed from the deck.
+        assert_eq!(deck.len(), 52 - 2);
+    }
+
+    #[test]
+    fn test_calculate_hand() {
+        // Test a hand with numerical cards.
+        let value = hand(&[Rank::Two, Rank::Five, Rank::Eight]).value();
+        assert_eq!(value.total, 15);
+        assert!(!value.soft);
+
+        // Test a hand with face cards.
+        let value = hand(&[Rank::King, Rank::Queen, Rank::Jack]).value();
+        assert_eq!(value.total, 30);
+        assert!(value.is_bust);
+
+        // Test a hand with an Ace.
+        let value = hand(&[Rank::Ace, Rank::Nine]).value();
+        assert_eq!(value.total, 20);
+        assert!(value.soft);
+
+        // Test a hand with multiple Aces.
+        let value = hand(&[Rank::Ace, Rank::Ace, Rank::Six]).value();
+        assert_eq!(value.total, 18);
+        assert!(value.soft);
+
+        // Both Aces have to count as 1 here.
+        let value = hand(&[Rank::Ace, Rank::Ace, Rank::King, Rank::Nine]).value();
+        assert_eq!(value.total, 21);
+        assert!(!value.soft);
+        assert!(!value.is_bust);
+
+        let value = hand(&[Rank::Ace, Rank::Ace, Rank::Ace, Rank::Ace]).value();
+        assert_eq!(value.total, 14);
+        assert!(value.soft);
+    }
+
+    #[test]
+    fn test_is_pair() {
+        assert!(hand(&[Rank::Eight, Rank::Eight]).is_pair());
+        assert!(hand(&[Rank::King, Rank::Ten]).is_pair());
+        assert!(hand(&[Rank::Ace, Rank::Ace]).is_pair());
+        assert!(!hand(&[Rank::Ace, Rank::King]).is_pair());
+        assert!(!hand(&[Rank::Eight, Rank::Eight, Rank::Eight]).is_pair());
+        assert!(!hand(&[Rank::Eight]).is_pair());
+    }
+
+    #[test]
+    fn test_blackjack() {
+        assert!(hand(&[Rank::Ace, Rank::King]).value().is_blackjack);
+        assert!(hand(&[Rank::Ten, Rank::Ace]).value().is_blackjack);
+
+        // Three cards to 21 is not a blackjack.
+        let value = hand(&[Rank::Seven, Rank::Seven, Rank::Seven]).value();
+        assert_eq!(value.total, 21);
+        assert!(!value.is_blackjack);
+
+        assert!(!Hand::new().value().is_blackjack);
+    }
+
+    // Tries every way of counting the Aces and keeps the best total that does not bust.
+    fn reference_value(ranks: &[Rank]) -> (i32, bool) {
+        let aces = ranks.iter().filter(|&&rank| rank == Rank::Ace).count();
+        let base: i32 = ranks
+            .iter()
+            .map(|&rank| if rank == Rank::Ace { 1 } else { rank.value() })
+            .sum();
+        let counts: Vec<(i32, bool)> = (0..=aces as i32)
+            .map(|elevens| (base + elevens * 10, elevens > 0))
+            .collect();
+        counts
+            .iter()
+            .filter(|(total, _)| *total <= 21)
+            .max()
+            .copied()
+            .unwrap_or((base, false))
+    }
+
+    #[test]
+    fn test_every_two_and_three_card_hand() {
+        for &first in Rank::ALL.iter() {
+            for &second in Rank::ALL.iter() {
+                let ranks = [first, second];
+                let value = hand(&ranks).value();
+                assert_eq!((value.total, value.soft), reference_value(&ranks));
+                assert!(!value.is_bust);
+                assert_eq!(
+                    value.is_blackjack,
+                    value.total == 21,
+                    "{:?} {:?}",
+                    first,
+                    second
+                );
+
+                for &third in Rank::ALL.iter() {
+                    let ranks = [first, second, third];
+                    let value = hand(&ranks).value();
+                    let (total, soft) = reference_value(&ranks);
+                    assert_eq!(value.total, total, "{:?}", ranks);
+                    assert_eq!(value.soft, soft, "{:?}", ranks);
+                    assert_eq!(value.is_bust, total > 21, "{:?}", ranks);
+                    assert!(!value.is_blackjack);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Blackjack engine.
+//
+// The library holds all of the game rules and state with no console I/O, so that
+// simulators, bots and services can link it directly. Player choices come from a
+// `DecisionSource`: the console one works over any reader and writer, and the
+// `project-leblanbe` binary is a thin interactive front-end on top of it.
+
+pub mod bankroll;
+pub mod card;
+pub mod config;
+pub mod dealer;
+pub mod decision;
+pub mod event;
+pub mod game;
+pub mod hand;
+pub mod player;
+pub mod profile;
+pub mod round;
+pub mod rules;
+pub mod seat;
+pub mod settle;
+pub mod shoe;
+pub mod strategy;
+
+pub use bankroll::{Bankroll, BetError};
+pub use card::{create_shuffled_deck, new_deck, Card, ParseCardError, Rank, Suit};
+pub use config::{Config, ConfigError, DisplayOptions, PlayerConfig};
+pub use dealer::DealerHand;
+pub use decision::{
+    ConsoleDecisions, DecisionSource, ScriptedDecisions, StrategyBot, TableDecisions,
+};
+pub use event::{
+    ConsoleRenderer, EventLog, EventSubscriber, GameEvent, Participant, PlayerStats, SessionStats,
+};
+pub use game::{Game, SeatError, MAX_SEATS};
+pub use hand::{deal_card, Hand, HandValue};
+pub use player::Player;
+pub use profile::{Preferences, Profile, ProfileError, Profiles};
+pub use round::{Action, ActionError, Round, RoundState};
+pub use rules::{
+    BlackjackPayout, HoleCard, ParseRulesError, RuleTokenError, Rules, Surrender, MAX_SPLIT_HANDS,
+};
+pub use seat::{Seat, SeatHand};
+pub use settle::{
+    settle, settle_even_money, settle_insurance, settle_surrender, Outcome, Settlement,
+};
+pub use shoe::Shoe;
+pub use strategy::{basic_strategy, Strategy};
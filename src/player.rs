@@ -0,0 +1,29 @@
+use crate::bankroll::Bankroll;
+
+/// Someone at the table. A player bets from one bankroll on every seat they play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    name: String,
+    pub(crate) bankroll: Bankroll,
+}
+
+impl Player {
+    pub fn new(name: impl Into<String>, balance: i32) -> Player {
+        Player {
+            name: name.into(),
+            bankroll: Bankroll::new(balance),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn bankroll(&self) -> &Bankroll {
+        &self.bankroll
+    }
+
+    pub fn balance(&self) -> i32 {
+        self.bankroll.balance()
+    }
+}
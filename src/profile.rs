@@ -0,0 +1,453 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::event::{EventSubscriber, GameEvent, SessionStats};
+use crate::game::Game;
+
+/// Why the profiles file could not be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileError {
+    // The file exists but could not be read.
+    Read { path: String, message: String },
+    // The file is not valid TOML or has fields of the wrong type.
+    Parse { path: String, message: String },
+    // The profiles could not be written back.
+    Write { path: String, message: String },
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::Read { path, message } => {
+                write!(f, "cannot read profiles file '{}': {}", path, message)
+            }
+            ProfileError::Parse { path, message } => {
+                write!(
+                    f,
+                    "invalid profiles file '{}': {}",
+                    path,
+                    message.trim_end()
+                )
+            }
+            ProfileError::Write { path, message } => {
+                write!(f, "cannot save profiles file '{}': {}", path, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+/// How a player likes the console game set up. Anything left unset keeps the
+/// session's settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Preferences {
+    // Whether to print the rules at the start instead of being asked.
+    pub show_rules: Option<bool>,
+    // Whether to print the statistics at the end.
+    pub show_stats: Option<bool>,
+}
+
+/// What is kept about a player from one session to the next.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    // The balance after the last round they played.
+    pub balance: i32,
+    // Their results over every session.
+    pub stats: SessionStats,
+    pub preferences: Preferences,
+}
+
+/// The profiles of every player who has sat down, by name, kept in a TOML file.
+///
+/// Subscribed to a game's events it adds each player's results to their lifetime
+/// stats, and `record` and `save` write their balances back after every round.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profiles {
+    #[serde(skip)]
+    path: PathBuf,
+    players: BTreeMap<String, Profile>,
+    // The name of the player on each seat of the game being played.
+    #[serde(skip)]
+    seats: Vec<String>,
+}
+
+impl Profiles {
+    /// Profiles kept in the given file, with nobody in them yet.
+    pub fn new(path: impl Into<PathBuf>) -> Profiles {
+        Profiles {
+            path: path.into(),
+            ..Profiles::default()
+        }
+    }
+
+    /// Reads the profiles kept in a file. A file that doesn't exist yet holds no
+    /// profiles and is created on the first save.
+    pub fn load(path: &Path) -> Result<Profiles, ProfileError> {
+        let name = path.display().to_string();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Profiles::new(path)),
+            Err(err) => {
+                return Err(ProfileError::Read {
+                    path: name,
+                    message: err.to_string(),
+                })
+            }
+        };
+        let profiles: Profiles = toml::from_str(&text).map_err(|err| ProfileError::Parse {
+            path: name,
+            message: err.to_string(),
+        })?;
+        Ok(Profiles {
+            path: path.to_path_buf(),
+            ..profiles
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.players.get(name)
+    }
+
+    pub fn players(&self) -> &BTreeMap<String, Profile> {
+        &self.players
+    }
+
+    /// Brings every player's saved balance to the table, and the preferences of the
+    /// first person at the console. A player whose saved balance can't cover the
+    /// table minimum starts over with the configured balance.
+    pub fn apply(&self, config: &mut Config) {
+        let players = config.table_players();
+        for (index, player) in players.iter().enumerate() {
+            let Some(profile) = self.get(&player.name) else {
+                continue;
+            };
+            if profile.balance < config.rules.min_bet {
+                continue;
+            }
+            // With no players configured the single player's balance is the session's.
+            match config.players.get_mut(index) {
+                Some(entry) => entry.balance = Some(profile.balance),
+                None => config.balance = profile.balance,
+            }
+        }
+        let human = players
+            .iter()
+            .find(|player| !player.is_bot())
+            .and_then(|player| self.get(&player.name));
+        if let Some(profile) = human {
+            let preferences = profile.preferences;
+            if preferences.show_rules.is_some() {
+                config.display.show_rules = preferences.show_rules;
+            }
+            if let Some(show_stats) = preferences.show_stats {
+                config.display.show_stats = show_stats;
+            }
+        }
+    }
+
+    /// Notes who sits on each seat of the game, so their results go to the right
+    /// profile, and gives newcomers a profile.
+    pub fn sit(&mut self, game: &Game) {
+        self.seats = game
+            .seats()
+            .iter()
+            .map(|seat| game.players()[seat.player()].name().to_string())
+            .collect();
+        self.record(game);
+    }
+
+    /// Copies every player's balance into their profile.
+    pub fn record(&mut self, game: &Game) {
+        for player in game.players() {
+            self.players
+                .entry(player.name().to_string())
+                .or_default()
+                .balance = player.balance();
+        }
+    }
+
+    /// Writes the profiles as TOML, in the same form `load` reads.
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).expect("profiles can always be written as TOML")
+    }
+
+    /// Writes the profiles to their file. They are written to a temporary file next
+    /// to it first and then moved over it, so the file holds either the old profiles
+    /// or the new ones even if the game is killed part way through. Each save gets its
+    /// own temporary file, so sessions saving at the same time don't write over each
+    /// other's; the last one to finish wins.
+    pub fn save(&self) -> Result<(), ProfileError> {
+        let temp = temp_file(&self.path);
+        let write = || -> io::Result<()> {
+            let mut file = File::create_new(&temp)?;
+            file.write_all(self.to_toml().as_bytes())?;
+            file.sync_all()?;
+            fs::rename(&temp, &self.path)?;
+            sync_dir(&self.path)
+        };
+        write().map_err(|err| {
+            let _ = fs::remove_file(&temp);
+            ProfileError::Write {
+                path: self.path.display().to_string(),
+                message: err.to_string(),
+            }
+        })
+    }
+}
+
+// A temporary file next to `path` that no other save uses, named after the process
+// and a count of the saves it has made, e.g. "profiles.toml.4242.0.tmp".
+fn temp_file(path: &Path) -> PathBuf {
+    static SAVES: AtomicU32 = AtomicU32::new(0);
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(format!(
+        ".{}.{}.tmp",
+        process::id(),
+        SAVES.fetch_add(1, Ordering::Relaxed)
+    ));
+    PathBuf::from(temp)
+}
+
+// Flushes the directory holding `path`, so the rename itself survives a crash.
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> io::Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    File::open(dir)?.sync_all()
+}
+
+// Other platforms can't open a directory as a file; the rename is all there is.
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+impl EventSubscriber for Profiles {
+    fn on_event(&mut self, event: &GameEvent) {
+        let seat = match event {
+            GameEvent::Settled { seat, .. } | GameEvent::PlayerBusts { seat, .. } => *seat,
+            _ => return,
+        };
+        if let Some(name) = self.seats.get(seat) {
+            self.players
+                .entry(name.clone())
+                .or_default()
+                .stats
+                .on_event(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::PlayerConfig;
+    use crate::decision::{ScriptedDecisions, StrategyBot, TableDecisions};
+    use crate::round::Action;
+    use crate::shoe;
+    use crate::strategy::Strategy;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("blackjack-{}-{}.toml", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_load() {
+        // A file that isn't there yet is an empty set of profiles.
+        let path = temp_path("no-profiles");
+        let profiles = Profiles::load(&path).unwrap();
+        assert_eq!(profiles, Profiles::new(&path));
+        assert!(!path.exists());
+
+        let path = temp_path("profiles-load");
+        fs::write(
+            &path,
+            r#"
+            [players.Alice]
+            balance = 250
+
+            [players.Alice.stats]
+            rounds = 3
+            wins = 2
+            net = 150
+
+            [players.Alice.preferences]
+            show_rules = false
+            "#,
+        )
+        .unwrap();
+        let profiles = Profiles::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let alice = profiles.get("Alice").unwrap();
+        assert_eq!(alice.balance, 250);
+        assert_eq!(alice.stats.rounds, 3);
+        assert_eq!(alice.stats.net, 150);
+        assert_eq!(alice.preferences.show_rules, Some(false));
+        assert_eq!(alice.preferences.show_stats, None);
+        assert_eq!(profiles.path(), path);
+
+        let path = temp_path("profiles-invalid");
+        fs::write(&path, "[players.Alice]\nbalance = \"lots\"\n").unwrap();
+        let result = Profiles::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(ProfileError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_save() {
+        let path = temp_path("profiles-save");
+        let mut profiles = Profiles::new(&path);
+        profiles.record(&Game::new(120));
+        profiles.save().unwrap();
+        // Saving again replaces the file, and nothing is left behind next to it.
+        profiles.save().unwrap();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        let leftovers = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|file| file.starts_with(name) && file.ends_with(".tmp"))
+            .count();
+        assert_eq!(leftovers, 0);
+        let loaded = Profiles::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, profiles);
+        assert_eq!(loaded.get("Player").unwrap().balance, 120);
+
+        // A failed save leaves no temporary file and reports where it was going.
+        let path = temp_path("no-such-dir").join("profiles.toml");
+        let err = Profiles::new(&path).save().unwrap_err();
+        assert!(matches!(err, ProfileError::Write { .. }));
+        assert!(err.to_string().contains("cannot save profiles file"));
+    }
+
+    #[test]
+    fn test_temp_file() {
+        // Every save, in this process or another, writes to a file of its own.
+        let path = Path::new("saves/profiles.toml");
+        let first = temp_file(path);
+        let second = temp_file(path);
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), path.parent());
+        let name = first.file_name().unwrap().to_str().unwrap();
+        let prefix = format!("profiles.toml.{}.", process::id());
+        assert!(name.starts_with(&prefix) && name.ends_with(".tmp"));
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut profiles = Profiles::new(temp_path("profiles-apply"));
+        for (name, balance) in [("Player", 250), ("Alice", 40), ("Bob", 0)] {
+            profiles.players.insert(
+                name.to_string(),
+                Profile {
+                    balance,
+                    ..Profile::default()
+                },
+            );
+        }
+        profiles.players.get_mut("Alice").unwrap().preferences = Preferences {
+            show_rules: Some(true),
+            show_stats: Some(true),
+        };
+
+        // The single player picks up where they left off.
+        let mut config = Config::default();
+        profiles.apply(&mut config);
+        assert_eq!(config.balance, 250);
+        assert_eq!(config.display, Config::default().display);
+
+        // Bob went broke and starts over; Carol is new. Alice is the first person at
+        // the console, so her preferences are used.
+        let mut config = Config {
+            players: vec![
+                PlayerConfig::bot("Bob", Strategy::Basic),
+                PlayerConfig::new("Alice"),
+                PlayerConfig::new("Carol"),
+            ],
+            ..Config::default()
+        };
+        profiles.apply(&mut config);
+        let balances: Vec<Option<i32>> =
+            config.players.iter().map(|player| player.balance).collect();
+        assert_eq!(balances, vec![None, Some(40), None]);
+        assert_eq!(config.balance, 100);
+        assert_eq!(config.display.show_rules, Some(true));
+        assert!(config.display.show_stats);
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn test_play() {
+        // Alice hits her 11 to 20 and the bot stands on 19 against the dealer's 17.
+        let mut game = Game::table(shoe::stacked(&["6H", "10S", "7D", "5C", "9C", "10D", "9H"]));
+        let alice = game.add_player("Alice", 100);
+        let bot = game.add_player("Bot", 100);
+        game.add_seat(alice).unwrap();
+        game.add_seat(bot).unwrap();
+        let mut decisions = TableDecisions::new()
+            .with_player(Box::new(
+                ScriptedDecisions::new()
+                    .with_bets([10])
+                    .with_actions([Action::Hit, Action::Stand]),
+            ))
+            .with_player(Box::new(StrategyBot::new(25)));
+
+        let mut profiles = Profiles::new(temp_path("profiles-play"));
+        profiles.players.insert(
+            "Alice".to_string(),
+            Profile {
+                balance: 100,
+                stats: SessionStats {
+                    rounds: 4,
+                    wins: 1,
+                    losses: 3,
+                    net: -20,
+                    ..SessionStats::default()
+                },
+                ..Profile::default()
+            },
+        );
+        profiles.sit(&game);
+        let profiles = Rc::new(RefCell::new(profiles));
+        game.subscribe(Box::new(profiles.clone()));
+        game.play_round(&mut decisions).unwrap().unwrap();
+        profiles.borrow_mut().record(&game);
+
+        let profiles = profiles.borrow();
+        let alice = profiles.get("Alice").unwrap();
+        assert_eq!(alice.balance, 110);
+        assert_eq!(
+            alice.stats,
+            SessionStats {
+                rounds: 5,
+                wins: 2,
+                losses: 3,
+                net: -10,
+                ..SessionStats::default()
+            }
+        );
+        let bot = profiles.get("Bot").unwrap();
+        assert_eq!(bot.balance, 125);
+        assert_eq!(bot.stats.wins, 1);
+    }
+}
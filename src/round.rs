@@ -0,0 +1,1948 @@
+use std::fmt;
+
+use crate::bankroll::{Bankroll, BetError};
+use crate::card::Rank;
+use crate::dealer::DealerHand;
+use crate::event::{GameEvent, Participant};
+use crate::game::Game;
+use crate::hand::{Hand, HandValue};
+use crate::player::Player;
+use crate::rules::{HoleCard, Rules, Surrender};
+use crate::seat::{Seat, SeatHand};
+use crate::settle::{settle, settle_even_money, settle_insurance, Outcome, Settlement};
+
+/// Where a round currently is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundState {
+    // Waiting for the bet on the given seat.
+    Betting(usize),
+    // The bets are placed and the opening cards are about to be dealt.
+    Dealing,
+    // Under early surrender the dealer shows an Ace or a ten, and the given seat can
+    // give up its hand before insurance is offered and the dealer checks for blackjack.
+    EarlySurrender(usize),
+    // The dealer shows an Ace and the given seat is offered insurance before the
+    // dealer checks for blackjack.
+    Insurance(usize),
+    // Waiting for a decision on the given seat and hand.
+    PlayerTurn(usize, usize),
+    // Every player hand is finished and the dealer plays out their hand.
+    DealerTurn,
+    // The round is over and the bets have been settled.
+    Settled,
+}
+
+/// A step that moves a round forward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    // Place a bet of the given amount.
+    Bet(i32),
+    // Leave the seat without a bet for this round.
+    SitOut,
+    // Deal the opening cards.
+    Deal,
+    // Place an insurance bet of the given amount, up to half of the main bet.
+    Insurance(i32),
+    // Turn insurance down.
+    DeclineInsurance,
+    // Keep the hand rather than surrendering it early.
+    DeclineSurrender,
+    // Settle a natural at 1:1 straight away instead of risking a push.
+    EvenMoney,
+    // Draw another card.
+    Hit,
+    // Keep the current hand.
+    Stand,
+    // Double the wager on a two-card hand, take exactly one more card and stand.
+    Double,
+    // Split a pair into two hands, each with its own wager.
+    Split,
+    // Give up the first two cards for half of the wager back.
+    Surrender,
+    // Play out the dealer's hand and settle the bets.
+    PlayDealer,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Bet(amount) => write!(f, "bet ${}", amount),
+            Action::SitOut => write!(f, "sit out"),
+            Action::Deal => write!(f, "deal"),
+            Action::Insurance(amount) => write!(f, "insure for ${}", amount),
+            Action::DeclineInsurance => write!(f, "decline insurance"),
+            Action::DeclineSurrender => write!(f, "decline surrender"),
+            Action::EvenMoney => write!(f, "take even money"),
+            Action::Hit => write!(f, "hit"),
+            Action::Stand => write!(f, "stand"),
+            Action::Double => write!(f, "double"),
+            Action::Split => write!(f, "split"),
+            Action::Surrender => write!(f, "surrender"),
+            Action::PlayDealer => write!(f, "play dealer"),
+        }
+    }
+}
+
+/// Why an action was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionError {
+    // The action cannot be taken in the round's current state.
+    Illegal { action: Action, state: RoundState },
+    // The bet amount was refused.
+    InvalidBet(BetError),
+    // The insurance bet was not between 1 and `max`.
+    InvalidInsurance { max: i32 },
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::Illegal { action, state } => {
+                write!(f, "cannot {} while the round is in {:?}", action, state)
+            }
+            ActionError::InvalidBet(err) => write!(f, "{}", err),
+            ActionError::InvalidInsurance { max } => {
+                write!(f, "insurance must be between $1 and ${}", max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+impl From<BetError> for ActionError {
+    fn from(err: BetError) -> ActionError {
+        ActionError::InvalidBet(err)
+    }
+}
+
+/// A single round of play, driven one action at a time with `apply`.
+///
+/// Every seat bets in turn, the opening cards are dealt around the table from the one
+/// shoe, each seat plays out its hands in turn and every seat is settled against the
+/// same dealer hand. Accessors like `seat` and `balance` describe the seat whose turn
+/// it is, or the first seat once the players are done.
+///
+/// Bets are taken from the bankroll when they are placed and paid back at settlement.
+/// A round dropped before it is settled returns its bets to the bankrolls.
+pub struct Round<'g> {
+    game: &'g mut Game,
+    state: RoundState,
+    settlements: Vec<Settlement>,
+    // The insurance bet on each seat, taken from the bankroll like the main bet.
+    insurance: Vec<i32>,
+    // Whether each seat took even money on a natural.
+    even_money: Vec<bool>,
+    // Whether each seat turned down early surrender, which rules it out for the hand.
+    declined_surrender: Vec<bool>,
+}
+
+impl<'g> Round<'g> {
+    pub(crate) fn new(game: &'g mut Game) -> Round<'g> {
+        // The last round's cards go to the discard tray.
+        for seat in game.seats.iter_mut() {
+            for seat_hand in seat.hands() {
+                game.shoe.discard(seat_hand.hand.cards());
+            }
+            seat.clear();
+        }
+        game.shoe.discard(game.dealer.cards());
+        game.dealer.clear();
+
+        // The shoe is only shuffled between rounds, once the cut card has come out.
+        if game.shoe.needs_shuffle() {
+            game.shoe.shuffle();
+            game.emit(GameEvent::ShoeShuffled {
+                cards: game.shoe.remaining(),
+            });
+        }
+
+        let seats = game.seats.len();
+        let state = if seats == 0 {
+            RoundState::Settled
+        } else {
+            RoundState::Betting(0)
+        };
+        Round {
+            game,
+            state,
+            settlements: Vec::new(),
+            insurance: vec![0; seats],
+            even_money: vec![false; seats],
+            declined_surrender: vec![false; seats],
+        }
+    }
+}
+
+impl Round<'_> {
+    pub fn state(&self) -> RoundState {
+        self.state
+    }
+
+    /// The index of the seat that is betting, being offered insurance or playing, or 0
+    /// at any other point in the round.
+    pub fn seat_index(&self) -> usize {
+        match self.state {
+            RoundState::Betting(seat)
+            | RoundState::EarlySurrender(seat)
+            | RoundState::Insurance(seat)
+            | RoundState::PlayerTurn(seat, _) => seat,
+            _ => 0,
+        }
+    }
+
+    /// The seat whose turn it is. See `seat_index`.
+    pub fn seat(&self) -> &Seat {
+        &self.game.seats[self.seat_index()]
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.game.seats
+    }
+
+    /// The name of the seat whose turn it is. See `Game::seat_label`.
+    pub fn seat_label(&self) -> String {
+        self.game.seat_label(self.seat_index())
+    }
+
+    /// The index of the player at the seat whose turn it is.
+    pub fn player_index(&self) -> usize {
+        self.seat().player()
+    }
+
+    /// The player at the seat whose turn it is.
+    pub fn player(&self) -> &Player {
+        &self.game.players[self.player_index()]
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.game.players
+    }
+
+    pub fn dealer(&self) -> &DealerHand {
+        &self.game.dealer
+    }
+
+    /// The bankroll of the player whose turn it is.
+    pub fn bankroll(&self) -> &Bankroll {
+        self.player().bankroll()
+    }
+
+    /// The hand waiting for a decision, if it is a player's turn or their chance to
+    /// surrender early.
+    pub fn current_hand(&self) -> Option<&Hand> {
+        let (seat, hand) = match self.state {
+            RoundState::PlayerTurn(seat, hand) => (seat, hand),
+            RoundState::EarlySurrender(seat) => (seat, 0),
+            _ => return None,
+        };
+        self.game.seats[seat].hand(hand).map(|hand| &hand.hand)
+    }
+
+    /// The wager on the seat's first hand: the bet placed at the start of the round, or
+    /// twice that once the hand is doubled.
+    pub fn bet(&self) -> i32 {
+        self.seat().hand(0).map_or(0, |hand| hand.wager)
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.game.rules
+    }
+
+    /// The insurance bet on the seat, or 0 if none was placed.
+    pub fn insurance(&self) -> i32 {
+        self.insurance[self.seat_index()]
+    }
+
+    /// The balance of the player whose turn it is.
+    pub fn balance(&self) -> i32 {
+        self.player().balance()
+    }
+
+    /// Checks that the player whose turn it is may place the given bet, both by their
+    /// balance and by the table limits.
+    pub fn check_bet(&self, bet: i32) -> Result<(), BetError> {
+        self.game.check_player_bet(self.player_index(), bet)
+    }
+
+    /// How every hand was settled once the round is over, seat by seat. Each seat's
+    /// hands come first, followed by its insurance bet if there was one.
+    pub fn settlements(&self) -> &[Settlement] {
+        &self.settlements
+    }
+
+    /// The outcome of the first hand settled, once the round is over.
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.settlements
+            .first()
+            .map(|settlement| settlement.outcome)
+    }
+
+    /// Lists the actions that `apply` accepts in the current state.
+    ///
+    /// While betting, the listed `Bet` carries the largest allowed amount; any amount
+    /// from the table minimum up to it is accepted. A player who can't cover the
+    /// minimum can only sit out. `Insurance` carries its largest
+    /// amount the same way, from 1 up. When insurance is
+    /// offered, the way to take it (`Insurance` or, on a natural, `EvenMoney`) comes
+    /// first.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        match self.state {
+            RoundState::Betting(_) => {
+                let rules = &self.game.rules;
+                if self.balance() < rules.min_bet {
+                    return vec![Action::SitOut];
+                }
+                let max = rules.max_bet.unwrap_or(i32::MAX);
+                vec![Action::Bet(self.balance().min(max)), Action::SitOut]
+            }
+            RoundState::Dealing => vec![Action::Deal],
+            RoundState::EarlySurrender(_) => vec![Action::Surrender, Action::DeclineSurrender],
+            RoundState::Insurance(_) => {
+                let take = if self.player_hand().value().is_blackjack {
+                    Action::EvenMoney
+                } else {
+                    Action::Insurance(self.max_insurance())
+                };
+                vec![take, Action::DeclineInsurance]
+            }
+            RoundState::PlayerTurn(_, _) => {
+                let mut actions = Vec::new();
+                if self.can_hit() {
+                    actions.push(Action::Hit);
+                }
+                actions.push(Action::Stand);
+                if self.check_double().is_ok() {
+                    actions.push(Action::Double);
+                }
+                if self.check_split().is_ok() {
+                    actions.push(Action::Split);
+                }
+                if self.can_surrender() {
+                    actions.push(Action::Surrender);
+                }
+                actions
+            }
+            RoundState::DealerTurn => vec![Action::PlayDealer],
+            RoundState::Settled => Vec::new(),
+        }
+    }
+
+    /// Applies an action and returns the state the round moved to.
+    pub fn apply(&mut self, action: Action) -> Result<RoundState, ActionError> {
+        match (self.state, action) {
+            (RoundState::Betting(seat), Action::Bet(amount)) => {
+                let player = self.player_index();
+                self.game.check_player_bet(player, amount)?;
+                self.game.players[player].bankroll.withdraw(amount);
+                self.game.seats[seat].place_bet(amount);
+                self.game.emit(GameEvent::BetPlaced { seat, amount });
+                self.take_bets(seat + 1);
+            }
+            (RoundState::Betting(seat), Action::SitOut) => self.take_bets(seat + 1),
+            (RoundState::Dealing, Action::Deal) => self.deal(),
+            (RoundState::EarlySurrender(seat), Action::Surrender) => {
+                self.seat_hand_mut().surrendered = true;
+                self.game.emit(GameEvent::PlayerSurrenders { seat });
+                self.offer_early_surrender(seat + 1);
+            }
+            (RoundState::EarlySurrender(seat), Action::DeclineSurrender) => {
+                self.declined_surrender[seat] = true;
+                self.offer_early_surrender(seat + 1)
+            }
+            (RoundState::Insurance(seat), Action::Insurance(amount)) => {
+                if self.player_hand().value().is_blackjack {
+                    return Err(self.illegal(action));
+                }
+                if amount <= 0 || amount > self.max_insurance() {
+                    return Err(ActionError::InvalidInsurance {
+                        max: self.max_insurance(),
+                    });
+                }
+                let player = self.player_index();
+                self.game.players[player].bankroll.withdraw(amount);
+                self.insurance[seat] = amount;
+                self.game.emit(GameEvent::InsuranceTaken { seat, amount });
+                self.offer_insurance(seat + 1);
+            }
+            (RoundState::Insurance(seat), Action::DeclineInsurance) => {
+                self.offer_insurance(seat + 1)
+            }
+            (RoundState::Insurance(seat), Action::EvenMoney)
+                if self.player_hand().value().is_blackjack =>
+            {
+                self.even_money[seat] = true;
+                self.game.emit(GameEvent::EvenMoneyTaken { seat });
+                self.offer_insurance(seat + 1);
+            }
+            (RoundState::PlayerTurn(_, _), Action::Hit) if self.can_hit() => {
+                self.deal_to_player();
+                let value = self.player_value();
+                if value.total >= 21 {
+                    // The player has reached 21 or busted, so the hand is finished.
+                    self.finish_hand(value.total);
+                }
+            }
+            (RoundState::PlayerTurn(_, _), Action::Stand) => {
+                let total = self.player_hand().value().total;
+                self.finish_hand(total);
+            }
+            (RoundState::PlayerTurn(seat, _), Action::Double) => {
+                self.check_double()?;
+                let wager = self.seat_hand().wager;
+                let player = self.player_index();
+                self.game.players[player].bankroll.withdraw(wager);
+                let seat_hand = self.seat_hand_mut();
+                seat_hand.wager += wager;
+                let wager = seat_hand.wager;
+                self.game.emit(GameEvent::PlayerDoubles { seat, wager });
+
+                // Exactly one more card, then the hand is finished.
+                self.deal_to_player();
+                let value = self.player_value();
+                self.finish_hand(value.total);
+            }
+            (RoundState::PlayerTurn(seat, hand), Action::Split) => {
+                self.check_split()?;
+                let wager = self.seat_hand().wager;
+                let player = self.player_index();
+                self.game.players[player].bankroll.withdraw(wager);
+                self.game.seats[seat].split(hand);
+                self.game.emit(GameEvent::PlayerSplits {
+                    seat,
+                    hands: self.game.seats[seat].hands().len(),
+                });
+                self.start_split_hand();
+            }
+            (RoundState::PlayerTurn(seat, _), Action::Surrender) if self.can_surrender() => {
+                self.seat_hand_mut().surrendered = true;
+                self.game.emit(GameEvent::PlayerSurrenders { seat });
+                self.start_turn(seat + 1);
+            }
+            (RoundState::DealerTurn, Action::PlayDealer) => self.play_dealer(),
+            (state, action) => return Err(ActionError::Illegal { action, state }),
+        }
+        Ok(self.state)
+    }
+
+    fn illegal(&self, action: Action) -> ActionError {
+        ActionError::Illegal {
+            action,
+            state: self.state,
+        }
+    }
+
+    // Split Aces get one card each unless the rules let them be hit.
+    fn can_hit(&self) -> bool {
+        !self.seat_hand().is_split_aces() || self.game.rules.hit_split_aces
+    }
+
+    // Insurance is capped at half of the main bet and at what the bankroll holds.
+    fn max_insurance(&self) -> i32 {
+        (self.seat_hand().wager / 2).min(self.balance())
+    }
+
+    // Surrender is only offered as the first decision on the first two cards, and only
+    // if the rules allow it. A hand kept when early surrender was offered is played out.
+    fn can_surrender(&self) -> bool {
+        self.game.rules.surrender != Surrender::NotOffered
+            && !self.declined_surrender[self.seat_index()]
+            && self.seat().hands().len() == 1
+            && self.player_hand().len() == 2
+    }
+
+    // Doubling is only allowed on the first two cards of a hand, after a split only if
+    // the rules allow it, and the bankroll has to cover a second wager of the same size.
+    fn check_double(&self) -> Result<(), ActionError> {
+        let seat_hand = self.seat_hand();
+        if seat_hand.hand.len() != 2
+            || !self.can_hit()
+            || (seat_hand.split && !self.game.rules.double_after_split)
+        {
+            return Err(self.illegal(Action::Double));
+        }
+        // Table limits only apply to the opening bet.
+        self.bankroll().check_bet(seat_hand.wager)?;
+        Ok(())
+    }
+
+    // A pair can be split while the seat is under the rules' hand limit, split Aces
+    // only if re-splitting Aces is allowed, and the bankroll has to cover the new hand.
+    fn check_split(&self) -> Result<(), ActionError> {
+        let seat_hand = self.seat_hand();
+        let rules = &self.game.rules;
+        if !seat_hand.hand.is_pair()
+            || self.seat().hands().len() >= rules.max_split_hands
+            || (seat_hand.is_split_aces() && !rules.resplit_aces)
+        {
+            return Err(self.illegal(Action::Split));
+        }
+        self.bankroll().check_bet(seat_hand.wager)?;
+        Ok(())
+    }
+
+    // Deals the second card to a hand made by splitting. Split Aces that can't be hit
+    // are finished straight away, unless they made another pair that can be split.
+    fn start_split_hand(&mut self) {
+        self.deal_to_player();
+        let value = self.player_value();
+        if !self.can_hit() {
+            if self.check_split().is_err() {
+                self.finish_hand(value.total);
+            }
+        } else if value.total == 21 {
+            self.finish_hand(value.total);
+        }
+    }
+
+    // Deals one card face up and announces it.
+    fn deal_to(&mut self, to: Participant) {
+        let Some(card) = self.game.shoe.draw() else {
+            return;
+        };
+        match to {
+            Participant::Seat(seat) => {
+                let hand = self.hand_index();
+                self.game.seats[seat]
+                    .hand_mut(hand)
+                    .expect("a bet was placed")
+                    .hand
+                    .push(card);
+            }
+            Participant::Dealer => self.game.dealer.push(card),
+        }
+        self.game.emit(GameEvent::CardDealt { to, card });
+    }
+
+    // Deals one card to the hand being played.
+    fn deal_to_player(&mut self) {
+        self.deal_to(Participant::Seat(self.seat_index()));
+    }
+
+    // The index of the hand being played, or of the first hand while dealing.
+    fn hand_index(&self) -> usize {
+        match self.state {
+            RoundState::PlayerTurn(_, hand) => hand,
+            _ => 0,
+        }
+    }
+
+    fn seat_hand(&self) -> &SeatHand {
+        self.seat()
+            .hand(self.hand_index())
+            .expect("a bet was placed")
+    }
+
+    fn seat_hand_mut(&mut self) -> &mut SeatHand {
+        let (seat, hand) = (self.seat_index(), self.hand_index());
+        self.game.seats[seat]
+            .hand_mut(hand)
+            .expect("a bet was placed")
+    }
+
+    fn player_hand(&self) -> &Hand {
+        &self.seat_hand().hand
+    }
+
+    // Works out the value of the current hand and announces it.
+    fn player_value(&mut self) -> HandValue {
+        let value = self.player_hand().value();
+        self.game.emit(GameEvent::HandTotal {
+            of: Participant::Seat(self.seat_index()),
+            value,
+        });
+        value
+    }
+
+    // Ends the current hand and moves on to the seat's next split hand, or to the next
+    // seat once every hand at this one has been played.
+    fn finish_hand(&mut self, total: i32) {
+        let seat = self.seat_index();
+        if total > 21 {
+            self.game.emit(GameEvent::PlayerBusts { seat, total });
+        } else {
+            self.game.emit(GameEvent::PlayerStands { seat, total });
+        }
+
+        let next = self.hand_index() + 1;
+        if next < self.game.seats[seat].hands().len() {
+            self.state = RoundState::PlayerTurn(seat, next);
+            self.game.emit(GameEvent::PlayingHand { seat, hand: next });
+            self.start_split_hand();
+        } else {
+            self.start_turn(seat + 1);
+        }
+    }
+
+    // Asks the seats from `first` on for their bets, then moves on to the deal. If no
+    // one bet there is nothing to deal and the round is over.
+    fn take_bets(&mut self, first: usize) {
+        self.state = if first < self.game.seats.len() {
+            RoundState::Betting(first)
+        } else if self.game.seats.iter().any(Seat::in_play) {
+            RoundState::Dealing
+        } else {
+            RoundState::Settled
+        };
+    }
+
+    // The seats with a bet, in the order they are dealt and play.
+    fn seats_in_play(&self) -> Vec<usize> {
+        (0..self.game.seats.len())
+            .filter(|&seat| self.game.seats[seat].in_play())
+            .collect()
+    }
+
+    // Deals the opening cards from the shoe in casino order: one card to each seat
+    // with a bet, the dealer's upcard, a second card to each seat, then the dealer's
+    // hole card.
+    fn deal(&mut self) {
+        let seats = self.seats_in_play();
+        for &seat in &seats {
+            self.deal_to(Participant::Seat(seat));
+        }
+        self.deal_to(Participant::Dealer);
+        for &seat in &seats {
+            self.deal_to(Participant::Seat(seat));
+        }
+        if self.game.rules.hole_card == HoleCard::Peek {
+            self.deal_hole_card();
+        }
+
+        for &seat in &seats {
+            self.game.emit(GameEvent::HandTotal {
+                of: Participant::Seat(seat),
+                value: self.game.seats[seat].hands()[0].hand.value(),
+            });
+        }
+
+        // Under early surrender the players get to give up their hands before the
+        // dealer checks for blackjack, and before insurance.
+        let rules = &self.game.rules;
+        if rules.surrender == Surrender::Early
+            && rules.hole_card == HoleCard::Peek
+            && self.game.dealer.peek().is_some()
+        {
+            self.offer_early_surrender(0);
+        } else {
+            self.offer_insurance_or_peek();
+        }
+    }
+
+    // Offers early surrender to the seats from `first` on. Naturals have no reason to
+    // surrender and are skipped. Once every seat has answered insurance is offered.
+    fn offer_early_surrender(&mut self, first: usize) {
+        for seat in first..self.game.seats.len() {
+            let Some(seat_hand) = self.game.seats[seat].hand(0) else {
+                continue;
+            };
+            if !seat_hand.hand.value().is_blackjack {
+                self.state = RoundState::EarlySurrender(seat);
+                return;
+            }
+        }
+        self.offer_insurance_or_peek();
+    }
+
+    // Insurance is offered under an Ace; otherwise the dealer checks for blackjack
+    // straight away.
+    fn offer_insurance_or_peek(&mut self) {
+        if self.game.dealer.upcard().map(|card| card.rank) == Some(Rank::Ace) {
+            self.offer_insurance(0);
+        } else {
+            self.check_for_blackjack();
+        }
+    }
+
+    // Offers insurance to the seats from `first` on that have something to take: even
+    // money on a natural, or an insurance bet they can afford. Surrendered hands have
+    // nothing to insure. Once every seat has answered the dealer checks for blackjack.
+    fn offer_insurance(&mut self, first: usize) {
+        for seat in first..self.game.seats.len() {
+            match self.game.seats[seat].hand(0) {
+                Some(seat_hand) if !seat_hand.surrendered => {}
+                _ => continue,
+            }
+            self.state = RoundState::Insurance(seat);
+            if self.player_hand().value().is_blackjack || self.max_insurance() > 0 {
+                return;
+            }
+        }
+        self.check_for_blackjack();
+    }
+
+    // Checks the dealer for blackjack once the cards are dealt and any insurance is
+    // placed, then starts the first seat's turn.
+    fn check_for_blackjack(&mut self) {
+        if self.peek() {
+            return;
+        }
+        self.start_turn(0);
+    }
+
+    // Starts the turn of the first seat from `first` on with a hand to play. A natural
+    // has no decision to make, nor does a natural already paid even money or a hand
+    // surrendered early. Once every seat is done the dealer plays.
+    fn start_turn(&mut self, first: usize) {
+        for seat in first..self.game.seats.len() {
+            let Some(seat_hand) = self.game.seats[seat].hand(0) else {
+                continue;
+            };
+            if !seat_hand.hand.value().is_blackjack && !seat_hand.surrendered {
+                self.state = RoundState::PlayerTurn(seat, 0);
+                return;
+            }
+        }
+        self.finish_turns();
+    }
+
+    // Whether a hand is still in the running and the dealer has to play out their hand
+    // against it. A busted hand has already lost.
+    fn needs_dealer(&self, seat: usize, seat_hand: &SeatHand) -> bool {
+        let value = seat_hand.hand.value();
+        let natural = value.is_blackjack && !seat_hand.split;
+        let busted = value.total > 21;
+        !(self.even_money[seat] || seat_hand.surrendered || natural || busted)
+    }
+
+    // Moves on to the dealer once every seat has played. When only naturals, busted and
+    // surrendered hands are left the dealer, who has no blackjack, does not draw.
+    // Without a hole card the dealer only takes a second card to see whether a natural
+    // is a push or an insurance bet wins.
+    fn finish_turns(&mut self) {
+        let seats = self.seats_in_play();
+        let needs_dealer = seats.iter().any(|&seat| {
+            self.game.seats[seat]
+                .hands()
+                .iter()
+                .any(|seat_hand| self.needs_dealer(seat, seat_hand))
+        });
+        if needs_dealer {
+            self.state = RoundState::DealerTurn;
+            return;
+        }
+
+        let natural = seats.iter().any(|&seat| {
+            !self.even_money[seat] && self.game.seats[seat].hands()[0].hand.value().is_blackjack
+        });
+        let insured = seats.iter().any(|&seat| self.insurance[seat] > 0);
+        self.reveal_dealer();
+        if (natural || insured) && self.game.rules.hole_card == HoleCard::NoHoleCard {
+            self.deal_to(Participant::Dealer);
+        }
+        self.settle();
+    }
+
+    // Under an Ace or a ten the dealer checks for blackjack. A dealer blackjack ends the
+    // round before anyone acts, so only the original bets are lost. Returns whether the
+    // round ended.
+    fn peek(&mut self) -> bool {
+        if self.game.rules.hole_card == HoleCard::NoHoleCard {
+            return false;
+        }
+        let Some(blackjack) = self.game.dealer.peek() else {
+            return false;
+        };
+        self.game.emit(GameEvent::DealerPeeks { blackjack });
+        if blackjack {
+            self.play_dealer();
+        }
+        blackjack
+    }
+
+    // Deals the dealer's second card face down.
+    fn deal_hole_card(&mut self) {
+        if let Some(card) = self.game.shoe.draw() {
+            self.game.dealer.push(card);
+            self.game.emit(GameEvent::HoleCardDealt);
+        }
+    }
+
+    fn reveal_dealer(&mut self) {
+        self.game.dealer.reveal();
+        self.game.emit(GameEvent::DealerReveals {
+            cards: self.game.dealer.cards().to_vec(),
+            total: self.game.dealer.value().total,
+        });
+    }
+
+    // Turns over the hole card, plays out the dealer's hand and settles the bets.
+    fn play_dealer(&mut self) {
+        let shown = self.game.dealer.len();
+        self.reveal_dealer();
+
+        self.game.dealer.play(&mut self.game.shoe, &self.game.rules);
+        let drawn = self.game.dealer.cards()[shown..].to_vec();
+        for card in drawn {
+            self.game.emit(GameEvent::CardDealt {
+                to: Participant::Dealer,
+                card,
+            });
+        }
+        let value = self.game.dealer.value();
+        self.game.emit(GameEvent::HandTotal {
+            of: Participant::Dealer,
+            value,
+        });
+
+        self.settle();
+    }
+
+    // Pays out every hand at every seat against the dealer's hand and ends the round.
+    fn settle(&mut self) {
+        let game = &mut *self.game;
+        self.settlements.clear();
+        for (seat, seat_hands) in game.seats.iter().enumerate() {
+            for seat_hand in seat_hands.hands() {
+                let mut settlement = if self.even_money[seat] {
+                    settle_even_money(seat_hand.wager)
+                } else {
+                    settle(seat_hand, &game.dealer, &game.rules)
+                };
+                settlement.seat = seat;
+                self.settlements.push(settlement);
+            }
+            if self.insurance[seat] > 0 {
+                let mut settlement = settle_insurance(self.insurance[seat], &game.dealer);
+                settlement.seat = seat;
+                self.settlements.push(settlement);
+            }
+        }
+        self.state = RoundState::Settled;
+
+        // Every player with a bet settled has money paid back into their bankroll, even
+        // if it is only a push, so each of them gets their new balance.
+        let mut settled = vec![false; self.game.players.len()];
+        for settlement in self.settlements.clone() {
+            let player = self.game.seats[settlement.seat].player();
+            self.game.players[player]
+                .bankroll
+                .deposit(settlement.payout());
+            settled[player] = true;
+            self.game.emit(GameEvent::Settled {
+                seat: settlement.seat,
+                outcome: settlement.outcome,
+                delta: settlement.delta,
+            });
+        }
+        for (player, settled) in settled.into_iter().enumerate() {
+            if settled {
+                self.game.emit(GameEvent::BalanceChanged {
+                    player,
+                    balance: self.game.players[player].balance(),
+                });
+            }
+        }
+    }
+}
+
+impl Drop for Round<'_> {
+    // An unfinished round hands its bets back rather than losing them.
+    fn drop(&mut self) {
+        if self.state != RoundState::Settled {
+            for (seat, insurance) in self.game.seats.iter_mut().zip(&self.insurance) {
+                let bankroll = &mut self.game.players[seat.player()].bankroll;
+                for seat_hand in seat.hands_mut() {
+                    bankroll.deposit(seat_hand.wager);
+                    seat_hand.wager = 0;
+                }
+                bankroll.deposit(*insurance);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::event::EventLog;
+    use crate::rules::{BlackjackPayout, Rules};
+    use crate::shoe;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // A game whose shoe deals the given cards in order, e.g. "10H".
+    fn stacked_game(cards: &[&str]) -> Game {
+        Game::with_shoe(100, shoe::stacked(cards))
+    }
+
+    #[test]
+    fn test_round_flow() {
+        // Player 10 and 6, dealer 9 up and 8 in the hole.
+        let mut game = stacked_game(&["10H", "9C", "6D", "8S"]);
+        let mut round = game.start_round();
+        assert_eq!(round.state(), RoundState::Betting(0));
+        assert_eq!(
+            round.legal_actions(),
+            vec![Action::Bet(100), Action::SitOut]
+        );
+
+        assert_eq!(round.apply(Action::Bet(20)), Ok(RoundState::Dealing));
+        assert_eq!(round.bet(), 20);
+        assert_eq!(round.legal_actions(), vec![Action::Deal]);
+
+        // The player and the dealer get two cards each, but only the upcard is shown.
+        let state = round.apply(Action::Deal).unwrap();
+        assert_eq!(state, RoundState::PlayerTurn(0, 0));
+        assert_eq!(round.seat().hands()[0].hand.value().total, 16);
+        assert_eq!(round.dealer().len(), 2);
+        assert_eq!(round.dealer().visible_cards(), &["9C".parse().unwrap()]);
+
+        assert_eq!(
+            round.legal_actions(),
+            vec![Action::Hit, Action::Stand, Action::Double]
+        );
+        assert_eq!(round.apply(Action::Stand), Ok(RoundState::DealerTurn));
+
+        // The dealer stands on 17 and wins.
+        assert_eq!(round.apply(Action::PlayDealer), Ok(RoundState::Settled));
+        assert!(round.dealer().is_revealed());
+        assert_eq!(round.dealer().value().total, 17);
+        assert_eq!(round.outcome(), Some(Outcome::Lose));
+        assert!(round.legal_actions().is_empty());
+        assert_eq!(round.balance(), 80);
+    }
+
+    #[test]
+    fn test_dealer_peek() {
+        // A dealer blackjack under an Ace ends the round before the player acts, and the
+        // player loses only their original bet.
+        let log = Rc::new(RefCell::new(EventLog::new()));
+        let mut game = stacked_game(&["10H", "AC", "6D", "KS"]);
+        game.subscribe(Box::new(log.clone()));
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::Insurance(0)));
+        assert_eq!(
+            round.apply(Action::DeclineInsurance),
+            Ok(RoundState::Settled)
+        );
+        assert_eq!(round.outcome(), Some(Outcome::Lose));
+        assert_eq!(round.balance(), 80);
+        assert!(log
+            .borrow()
+            .events()
+            .contains(&GameEvent::DealerPeeks { blackjack: true }));
+
+        // The same under a ten-value upcard.
+        let mut game = stacked_game(&["10H", "QC", "6D", "AS"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::Settled));
+
+        // Without a blackjack in the hole play goes on as usual.
+        let log = Rc::new(RefCell::new(EventLog::new()));
+        let mut game = stacked_game(&["10H", "AC", "6D", "5S"]);
+        game.subscribe(Box::new(log.clone()));
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(
+            round.apply(Action::DeclineInsurance),
+            Ok(RoundState::PlayerTurn(0, 0))
+        );
+        assert!(log
+            .borrow()
+            .events()
+            .contains(&GameEvent::DealerPeeks { blackjack: false }));
+
+        // The dealer does not peek under any other upcard.
+        let log = Rc::new(RefCell::new(EventLog::new()));
+        let mut game = stacked_game(&["10H", "9C", "6D", "5S"]);
+        game.subscribe(Box::new(log.clone()));
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert!(!log
+            .borrow()
+            .events()
+            .iter()
+            .any(|event| matches!(event, GameEvent::DealerPeeks { .. })));
+    }
+
+    #[test]
+    fn test_hit_until_turn_ends() {
+        let mut game = stacked_game(&["2H", "9C", "3D", "8S", "4H", "5H", "6H", "3H"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+
+        // Hitting keeps the player's turn going until they reach 21 or bust.
+        while let RoundState::PlayerTurn(0, 0) = round.state() {
+            let cards = round.seat().hands()[0].hand.len();
+            round.apply(Action::Hit).unwrap();
+            assert_eq!(round.seat().hands()[0].hand.len(), cards + 1);
+        }
+        assert_eq!(round.seat().hands()[0].hand.value().total, 23);
+
+        // The busted hand has already lost, so the dealer turns over the hole card and
+        // draws nothing.
+        assert_eq!(round.state(), RoundState::Settled);
+        assert_eq!(round.dealer().len(), 2);
+        assert_eq!(round.settlements()[0].outcome, Outcome::Lose);
+    }
+
+    #[test]
+    fn test_illegal_actions() {
+        let mut game = Game::new(100);
+        let mut round = game.start_round();
+
+        // Nothing but a bet is allowed before the bet is placed.
+        assert_eq!(
+            round.apply(Action::Hit),
+            Err(ActionError::Illegal {
+                action: Action::Hit,
+                state: RoundState::Betting(0),
+            })
+        );
+        assert_eq!(
+            round.apply(Action::Deal),
+            Err(ActionError::Illegal {
+                action: Action::Deal,
+                state: RoundState::Betting(0),
+            })
+        );
+
+        // Bad bets are rejected and leave the round waiting for a bet.
+        assert_eq!(
+            round.apply(Action::Bet(0)),
+            Err(ActionError::InvalidBet(BetError::NotPositive))
+        );
+        assert_eq!(
+            round.apply(Action::Bet(101)),
+            Err(ActionError::InvalidBet(BetError::ExceedsBalance))
+        );
+        assert_eq!(round.state(), RoundState::Betting(0));
+
+        // A second bet is not allowed once one is placed.
+        round.apply(Action::Bet(10)).unwrap();
+        assert!(round.apply(Action::Bet(10)).is_err());
+        assert!(round.apply(Action::Stand).is_err());
+    }
+
+    #[test]
+    fn test_natural_blackjack() {
+        // A natural against a dealer 9 is paid 3:2 without the player taking a turn.
+        let log = Rc::new(RefCell::new(EventLog::new()));
+        let mut game = stacked_game(&["AH", "9C", "KD", "7S"]);
+        game.subscribe(Box::new(log.clone()));
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::Settled));
+        assert_eq!(round.outcome(), Some(Outcome::Blackjack));
+        assert_eq!(round.balance(), 115);
+
+        // The dealer turns over the hole card but does not draw to their 16.
+        assert_eq!(round.dealer().len(), 2);
+        assert!(log.borrow().events().contains(&GameEvent::Settled {
+            seat: 0,
+            outcome: Outcome::Blackjack,
+            delta: 15,
+        }));
+
+        // Other table payouts.
+        for (payout, balance) in [
+            (BlackjackPayout::SixToFive, 112),
+            (BlackjackPayout::EvenMoney, 110),
+        ] {
+            let mut game = stacked_game(&["AH", "9C", "KD", "7S"]);
+            game.set_rules(Rules {
+                blackjack_payout: payout,
+                ..Rules::default()
+            });
+            let mut round = game.start_round();
+            round.apply(Action::Bet(10)).unwrap();
+            round.apply(Action::Deal).unwrap();
+            assert_eq!(round.balance(), balance);
+        }
+
+        // A natural against a dealer natural is a push.
+        let mut game = stacked_game(&["AH", "AC", "KD", "QS"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(
+            round.apply(Action::DeclineInsurance),
+            Ok(RoundState::Settled)
+        );
+        assert_eq!(round.outcome(), Some(Outcome::Push));
+        assert_eq!(round.balance(), 100);
+
+        // Three cards to 21 is not a natural and the dealer still plays.
+        let mut game = stacked_game(&["7H", "10C", "7D", "6S", "7C", "5H"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(round.apply(Action::Hit), Ok(RoundState::DealerTurn));
+        round.apply(Action::PlayDealer).unwrap();
+        assert_eq!(round.dealer().value().total, 21);
+        assert_eq!(round.outcome(), Some(Outcome::Push));
+    }
+
+    #[test]
+    fn test_double() {
+        // 11 against a dealer 9: double, draw a 10 and beat the dealer's 17.
+        let mut game = stacked_game(&["6H", "9C", "5D", "8S", "10C"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(
+            round.legal_actions(),
+            vec![Action::Hit, Action::Stand, Action::Double]
+        );
+        assert_eq!(round.apply(Action::Double), Ok(RoundState::DealerTurn));
+        assert_eq!(round.bet(), 20);
+        assert_eq!(round.balance(), 80);
+        assert_eq!(round.seat().hands()[0].hand.len(), 3);
+
+        // Settlement pays on the doubled stake.
+        round.apply(Action::PlayDealer).unwrap();
+        assert_eq!(round.outcome(), Some(Outcome::Win));
+        assert_eq!(round.balance(), 120);
+
+        // A doubled hand that loses costs twice the bet.
+        let mut game = stacked_game(&["6H", "9C", "5D", "8S", "2C"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Double).unwrap();
+        round.apply(Action::PlayDealer).unwrap();
+        assert_eq!(round.settlements()[0].delta, -20);
+        assert_eq!(round.balance(), 80);
+    }
+
+    #[test]
+    fn test_double_not_allowed() {
+        // Only the first two cards can be doubled.
+        let mut game = stacked_game(&["2H", "9C", "3D", "8S", "4C", "5H"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Hit).unwrap();
+        assert_eq!(round.legal_actions(), vec![Action::Hit, Action::Stand]);
+        assert_eq!(
+            round.apply(Action::Double),
+            Err(ActionError::Illegal {
+                action: Action::Double,
+                state: RoundState::PlayerTurn(0, 0),
+            })
+        );
+
+        // The bankroll has to cover the second wager.
+        let mut game = Game::with_shoe(15, shoe::stacked(&["6H", "9C", "5D", "8S"]));
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert!(!round.legal_actions().contains(&Action::Double));
+        assert_eq!(
+            round.apply(Action::Double),
+            Err(ActionError::InvalidBet(BetError::ExceedsBalance))
+        );
+        assert_eq!(round.bet(), 10);
+
+        // Doubling is not a betting action.
+        let mut game = stacked_game(&[]);
+        let mut round = game.start_round();
+        assert!(round.apply(Action::Double).is_err());
+    }
+
+    #[test]
+    fn test_split() {
+        // A pair of eights against a dealer 6, who has 10 in the hole.
+        let mut game = stacked_game(&["8H", "6C", "8D", "10S", "3C", "10H", "9C", "7D"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert!(round.legal_actions().contains(&Action::Split));
+
+        // The first hand gets its second card straight away.
+        assert_eq!(round.apply(Action::Split), Ok(RoundState::PlayerTurn(0, 0)));
+        assert_eq!(round.balance(), 80);
+        assert_eq!(round.seat().hands().len(), 2);
+        assert_eq!(round.current_hand().unwrap().value().total, 11);
+
+        // 11 is doubled after the split, then play moves on to the second hand.
+        assert_eq!(
+            round.apply(Action::Double),
+            Ok(RoundState::PlayerTurn(0, 1))
+        );
+        assert_eq!(round.current_hand().unwrap().value().total, 17);
+        assert_eq!(round.apply(Action::Stand), Ok(RoundState::DealerTurn));
+
+        // The dealer busts and each hand is paid on its own wager.
+        round.apply(Action::PlayDealer).unwrap();
+        let deltas: Vec<(Outcome, i32)> = round
+            .settlements()
+            .iter()
+            .map(|settlement| (settlement.outcome, settlement.delta))
+            .collect();
+        assert_eq!(deltas, vec![(Outcome::Win, 20), (Outcome::Win, 10)]);
+        assert_eq!(round.balance(), 130);
+    }
+
+    #[test]
+    fn test_split_aces() {
+        // Split Aces get one card each; an Ace and a King after a split is only 21.
+        let mut game = stacked_game(&["AH", "9C", "AD", "8S", "KS", "5C"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(round.apply(Action::Split), Ok(RoundState::DealerTurn));
+        assert_eq!(round.seat().hands()[0].hand.value().total, 21);
+        assert_eq!(round.seat().hands()[1].hand.value().total, 16);
+
+        round.apply(Action::PlayDealer).unwrap();
+        let outcomes: Vec<Outcome> = round.settlements().iter().map(|s| s.outcome).collect();
+        assert_eq!(outcomes, vec![Outcome::Win, Outcome::Lose]);
+        assert_eq!(round.balance(), 100);
+
+        // Another Ace can only be split again if the rules allow it, and can't be hit.
+        let mut game = stacked_game(&["AH", "9C", "AD", "8S", "AC", "5C", "6D", "7H"]);
+        game.set_rules(Rules {
+            resplit_aces: true,
+            ..Rules::default()
+        });
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Split).unwrap();
+        assert_eq!(round.legal_actions(), vec![Action::Stand, Action::Split]);
+        assert!(round.apply(Action::Hit).is_err());
+        assert_eq!(round.apply(Action::Split), Ok(RoundState::DealerTurn));
+        assert_eq!(round.seat().hands().len(), 3);
+
+        // Split Aces can be hit where the rules allow it.
+        let mut game = stacked_game(&["AH", "9C", "AD", "8S", "5C", "2D"]);
+        game.set_rules(Rules {
+            hit_split_aces: true,
+            ..Rules::default()
+        });
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(round.apply(Action::Split), Ok(RoundState::PlayerTurn(0, 0)));
+        assert!(round.legal_actions().contains(&Action::Hit));
+    }
+
+    #[test]
+    fn test_split_limits() {
+        // A pair of eights, then another eight on the first hand.
+        let cards = ["8H", "9C", "8D", "8S", "8C", "2D", "3H", "4S"];
+
+        // The seat can't hold more hands than the rules allow.
+        let mut game = stacked_game(&cards);
+        game.set_rules(Rules {
+            max_split_hands: 2,
+            double_after_split: false,
+            ..Rules::default()
+        });
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Split).unwrap();
+        assert_eq!(round.legal_actions(), vec![Action::Hit, Action::Stand]);
+        assert_eq!(
+            round.apply(Action::Split),
+            Err(ActionError::Illegal {
+                action: Action::Split,
+                state: RoundState::PlayerTurn(0, 0),
+            })
+        );
+
+        // Up to the limit a pair can be split again.
+        let mut game = stacked_game(&cards);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Split).unwrap();
+        round.apply(Action::Split).unwrap();
+        assert_eq!(round.seat().hands().len(), 3);
+        assert_eq!(round.balance(), 70);
+
+        // The bankroll has to cover the new hand.
+        let mut game = Game::with_shoe(15, shoe::stacked(&cards));
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert!(!round.legal_actions().contains(&Action::Split));
+        assert_eq!(
+            round.apply(Action::Split),
+            Err(ActionError::InvalidBet(BetError::ExceedsBalance))
+        );
+
+        // Only a pair can be split.
+        let mut game = stacked_game(&["8H", "9C", "7D", "8S"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert!(round.apply(Action::Split).is_err());
+    }
+
+    // A game under the given surrender rule, dealing the given cards in order.
+    fn surrender_game(surrender: Surrender, cards: &[&str]) -> Game {
+        let mut game = stacked_game(cards);
+        game.set_rules(Rules {
+            surrender,
+            ..Rules::default()
+        });
+        game
+    }
+
+    #[test]
+    fn test_late_surrender() {
+        // Player 16 against a dealer 10 with 8 in the hole.
+        let cards = ["10H", "10C", "6D", "8S", "2C"];
+        let mut game = surrender_game(Surrender::Late, &cards);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert!(round.legal_actions().contains(&Action::Surrender));
+
+        // Half the bet comes back and the dealer doesn't draw.
+        assert_eq!(round.apply(Action::Surrender), Ok(RoundState::Settled));
+        assert_eq!(round.outcome(), Some(Outcome::Surrender));
+        assert_eq!(round.settlements()[0].delta, -5);
+        assert_eq!(round.balance(), 95);
+        assert!(round.dealer().is_revealed());
+        assert_eq!(round.dealer().len(), 2);
+        drop(round);
+
+        // Surrender is only the first decision.
+        let mut game = surrender_game(Surrender::Late, &cards);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Hit).unwrap();
+        assert!(!round.legal_actions().contains(&Action::Surrender));
+        assert!(round.apply(Action::Surrender).is_err());
+
+        // A dealer blackjack is checked for first and takes the whole bet.
+        let mut game = surrender_game(Surrender::Late, &["10H", "AC", "6D", "KS"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(
+            round.apply(Action::DeclineInsurance),
+            Ok(RoundState::Settled)
+        );
+        assert_eq!(round.outcome(), Some(Outcome::Lose));
+
+        // Tables that don't offer it never list it.
+        let mut game = stacked_game(&cards);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert!(!round.legal_actions().contains(&Action::Surrender));
+        assert!(round.apply(Action::Surrender).is_err());
+    }
+
+    #[test]
+    fn test_early_surrender() {
+        // Player 16 against a dealer Ace with a King in the hole.
+        let cards = ["10H", "AC", "6D", "KS"];
+
+        // The player can give up half before insurance and before the dealer checks
+        // for blackjack.
+        let mut game = surrender_game(Surrender::Early, &cards);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::EarlySurrender(0)));
+        assert_eq!(
+            round.legal_actions(),
+            vec![Action::Surrender, Action::DeclineSurrender]
+        );
+        assert_eq!(round.apply(Action::Surrender), Ok(RoundState::Settled));
+        assert_eq!(round.outcome(), Some(Outcome::Surrender));
+        assert_eq!(round.balance(), 95);
+        drop(round);
+
+        // Keeping the hand moves on to insurance, then the dealer checks and the
+        // blackjack ends the round.
+        let mut game = surrender_game(Surrender::Early, &cards);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(
+            round.apply(Action::DeclineSurrender),
+            Ok(RoundState::Insurance(0))
+        );
+        assert_eq!(
+            round.apply(Action::DeclineInsurance),
+            Ok(RoundState::Settled)
+        );
+        assert_eq!(round.outcome(), Some(Outcome::Lose));
+        assert_eq!(round.seat().hands()[0].hand.len(), 2);
+        drop(round);
+
+        // Without a dealer blackjack the hand is played as usual.
+        let mut game = surrender_game(Surrender::Early, &["10H", "AC", "6D", "7S", "2C"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::DeclineSurrender).unwrap();
+        assert_eq!(
+            round.apply(Action::DeclineInsurance),
+            Ok(RoundState::PlayerTurn(0, 0))
+        );
+        // Having kept the hand, the player can't surrender it after all.
+        assert!(!round.legal_actions().contains(&Action::Surrender));
+        assert_eq!(
+            round.apply(Action::Surrender),
+            Err(ActionError::Illegal {
+                action: Action::Surrender,
+                state: RoundState::PlayerTurn(0, 0),
+            })
+        );
+        assert_eq!(round.apply(Action::Hit), Ok(RoundState::PlayerTurn(0, 0)));
+        assert_eq!(round.current_hand().unwrap().value().total, 18);
+        drop(round);
+
+        // A ten up is surrendered against too, with no insurance to offer.
+        let mut game = surrender_game(Surrender::Early, &["10H", "KC", "6D", "AS"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::EarlySurrender(0)));
+        assert_eq!(round.apply(Action::Surrender), Ok(RoundState::Settled));
+        assert_eq!(round.outcome(), Some(Outcome::Surrender));
+        drop(round);
+
+        // With nothing to peek at, surrender is the player's first decision.
+        let mut game = surrender_game(Surrender::Early, &["10H", "9C", "6D", "7S"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::PlayerTurn(0, 0)));
+        assert_eq!(round.apply(Action::Surrender), Ok(RoundState::Settled));
+    }
+
+    #[test]
+    fn test_insurance() {
+        // Player 18 against a dealer Ace with a King in the hole.
+        let mut game = stacked_game(&["10H", "AC", "8D", "KS"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::Insurance(0)));
+        assert_eq!(
+            round.legal_actions(),
+            vec![Action::Insurance(10), Action::DeclineInsurance]
+        );
+
+        // Insurance is at most half of the bet.
+        for amount in [0, 11] {
+            assert_eq!(
+                round.apply(Action::Insurance(amount)),
+                Err(ActionError::InvalidInsurance { max: 10 })
+            );
+        }
+        assert_eq!(
+            round.apply(Action::Hit).unwrap_err().to_string(),
+            "cannot hit while the round is in Insurance(0)"
+        );
+
+        // The dealer blackjack takes the bet and insurance pays 2:1, so the player
+        // breaks even.
+        assert_eq!(round.apply(Action::Insurance(10)), Ok(RoundState::Settled));
+        assert_eq!(round.insurance(), 10);
+        let deltas: Vec<(Outcome, i32)> = round
+            .settlements()
+            .iter()
+            .map(|settlement| (settlement.outcome, settlement.delta))
+            .collect();
+        assert_eq!(
+            deltas,
+            vec![(Outcome::Lose, -20), (Outcome::InsuranceWin, 20)]
+        );
+        assert_eq!(round.balance(), 100);
+        drop(round);
+
+        // Without a dealer blackjack the insurance is lost and play goes on.
+        let mut game = stacked_game(&["10H", "AC", "8D", "7S"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(
+            round.apply(Action::Insurance(5)),
+            Ok(RoundState::PlayerTurn(0, 0))
+        );
+        assert_eq!(round.balance(), 75);
+        round.apply(Action::Stand).unwrap();
+        round.apply(Action::PlayDealer).unwrap();
+        assert_eq!(round.settlements()[1].outcome, Outcome::InsuranceLose);
+        assert_eq!(round.outcome(), Some(Outcome::Push));
+        assert_eq!(round.balance(), 95);
+        drop(round);
+
+        // An abandoned round hands the insurance back too.
+        let mut game = stacked_game(&["10H", "AC", "8D", "7S"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Insurance(10)).unwrap();
+        drop(round);
+        assert_eq!(game.balance(), 100);
+
+        // Insurance is only offered if the bankroll can cover some of it.
+        let mut game = Game::with_shoe(10, shoe::stacked(&["10H", "AC", "8D", "7S"]));
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::PlayerTurn(0, 0)));
+    }
+
+    #[test]
+    fn test_even_money() {
+        // A natural against an Ace is paid 1:1 straight away, whatever is in the hole.
+        for hole in ["QS", "9S"] {
+            let mut game = stacked_game(&["AH", "AC", "KD", hole]);
+            let mut round = game.start_round();
+            round.apply(Action::Bet(10)).unwrap();
+            round.apply(Action::Deal).unwrap();
+            assert_eq!(
+                round.legal_actions(),
+                vec![Action::EvenMoney, Action::DeclineInsurance]
+            );
+            assert!(round.apply(Action::Insurance(5)).is_err());
+            assert_eq!(round.apply(Action::EvenMoney), Ok(RoundState::Settled));
+            assert_eq!(round.outcome(), Some(Outcome::Win));
+            assert_eq!(round.balance(), 110);
+        }
+
+        // Turning it down risks a push for the full blackjack payout.
+        let mut game = stacked_game(&["AH", "AC", "KD", "9S"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(
+            round.apply(Action::DeclineInsurance),
+            Ok(RoundState::Settled)
+        );
+        assert_eq!(round.outcome(), Some(Outcome::Blackjack));
+        assert_eq!(round.balance(), 115);
+    }
+
+    #[test]
+    fn test_no_hole_card() {
+        // 11 against a dealer Ace, with no hole card dealt.
+        let mut game = stacked_game(&["6H", "AC", "5D", "10C", "KS"]);
+        game.set_rules(Rules::european());
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(round.dealer().len(), 1);
+        assert_eq!(
+            round.apply(Action::DeclineInsurance),
+            Ok(RoundState::PlayerTurn(0, 0))
+        );
+
+        // The dealer's second card makes a blackjack, which takes the doubled bet.
+        round.apply(Action::Double).unwrap();
+        assert_eq!(round.apply(Action::PlayDealer), Ok(RoundState::Settled));
+        assert!(round.dealer().value().is_blackjack);
+        assert_eq!(round.settlements()[0].delta, -20);
+        assert_eq!(round.balance(), 80);
+        drop(round);
+
+        // A natural waits for the dealer's second card, and pushes with a blackjack.
+        for (second, outcome) in [("AS", Outcome::Push), ("9S", Outcome::Blackjack)] {
+            let mut game = stacked_game(&["AH", "10C", "KD", second, "5C"]);
+            game.set_rules(Rules::european());
+            let mut round = game.start_round();
+            round.apply(Action::Bet(10)).unwrap();
+            assert_eq!(round.apply(Action::Deal), Ok(RoundState::Settled));
+            assert_eq!(round.outcome(), Some(outcome));
+            assert_eq!(round.dealer().len(), 2);
+        }
+
+        // Insurance is settled against the dealer's second card even once the hand has
+        // busted.
+        let mut game = stacked_game(&["10H", "AC", "6D", "KS", "QS"]);
+        game.set_rules(Rules::european());
+        let mut round = game.start_round();
+        round.apply(Action::Bet(20)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Insurance(10)).unwrap();
+        assert_eq!(round.apply(Action::Hit), Ok(RoundState::Settled));
+        assert!(round.dealer().value().is_blackjack);
+        let outcomes: Vec<Outcome> = round
+            .settlements()
+            .iter()
+            .map(|settlement| settlement.outcome)
+            .collect();
+        assert_eq!(outcomes, vec![Outcome::Lose, Outcome::InsuranceWin]);
+        assert_eq!(round.balance(), 100);
+    }
+
+    #[test]
+    fn test_table_limits() {
+        let mut game = stacked_game(&["10H", "9C", "6D", "8S"]);
+        game.set_rules(Rules {
+            min_bet: 10,
+            max_bet: Some(50),
+            ..Rules::default()
+        });
+        let mut round = game.start_round();
+        assert_eq!(round.legal_actions(), vec![Action::Bet(50), Action::SitOut]);
+        assert_eq!(
+            round.apply(Action::Bet(5)),
+            Err(ActionError::InvalidBet(BetError::BelowMinimum(10)))
+        );
+        assert_eq!(
+            round.apply(Action::Bet(60)),
+            Err(ActionError::InvalidBet(BetError::AboveMaximum(50)))
+        );
+
+        // Doubling a maximum bet goes over the limit, which is fine.
+        round.apply(Action::Bet(50)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert_eq!(round.apply(Action::Double), Ok(RoundState::DealerTurn));
+        assert_eq!(round.bet(), 100);
+    }
+
+    #[test]
+    fn test_dealer_hits_soft_17() {
+        // Player 18 against a dealer soft 17, with a 4 to come.
+        let cards = ["10H", "6C", "8D", "AS", "4H"];
+        for (hits_soft_17, outcome) in [(false, Outcome::Win), (true, Outcome::Lose)] {
+            let mut game = stacked_game(&cards);
+            game.set_rules(Rules {
+                dealer_hits_soft_17: hits_soft_17,
+                ..Rules::default()
+            });
+            let mut round = game.start_round();
+            round.apply(Action::Bet(10)).unwrap();
+            round.apply(Action::Deal).unwrap();
+            round.apply(Action::Stand).unwrap();
+            round.apply(Action::PlayDealer).unwrap();
+            assert_eq!(round.outcome(), Some(outcome));
+        }
+    }
+
+    #[test]
+    fn test_round_events() {
+        let log = Rc::new(RefCell::new(EventLog::new()));
+        // Player 10 and 8, dealer 6 up and 10 in the hole, then draws a 7 and busts.
+        let mut game = stacked_game(&["10H", "6C", "8D", "10S", "7H"]);
+        game.subscribe(Box::new(log.clone()));
+
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Stand).unwrap();
+        round.apply(Action::PlayDealer).unwrap();
+        assert_eq!(round.outcome(), Some(Outcome::Win));
+        drop(round);
+
+        let card = |card: &str| card.parse().unwrap();
+        let log = log.borrow();
+        let events = log.events();
+
+        // Player, dealer upcard, player, then the hole card face down.
+        assert_eq!(
+            events[..5],
+            [
+                GameEvent::BetPlaced {
+                    seat: 0,
+                    amount: 10
+                },
+                GameEvent::CardDealt {
+                    to: Participant::Seat(0),
+                    card: card("10H"),
+                },
+                GameEvent::CardDealt {
+                    to: Participant::Dealer,
+                    card: card("6C"),
+                },
+                GameEvent::CardDealt {
+                    to: Participant::Seat(0),
+                    card: card("8D"),
+                },
+                GameEvent::HoleCardDealt,
+            ]
+        );
+
+        // The hole card is only named once the dealer reveals it.
+        assert!(events.contains(&GameEvent::DealerReveals {
+            cards: vec![card("6C"), card("10S")],
+            total: 16,
+        }));
+        assert!(events.contains(&GameEvent::CardDealt {
+            to: Participant::Dealer,
+            card: card("7H"),
+        }));
+        assert!(events.contains(&GameEvent::HandTotal {
+            of: Participant::Dealer,
+            value: game.dealer().value(),
+        }));
+
+        // The settlement reports the balance change last.
+        assert_eq!(
+            events[events.len() - 2..],
+            [
+                GameEvent::Settled {
+                    seat: 0,
+                    outcome: Outcome::Win,
+                    delta: game.balance() - 100,
+                },
+                GameEvent::BalanceChanged {
+                    player: 0,
+                    balance: game.balance()
+                },
+            ]
+        );
+        drop(log);
+
+        // A push hands the bet back, so the balance is reported too.
+        let log = Rc::new(RefCell::new(EventLog::new()));
+        let mut game = stacked_game(&["10H", "9C", "8D", "9S"]);
+        game.subscribe(Box::new(log.clone()));
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Stand).unwrap();
+        round.apply(Action::PlayDealer).unwrap();
+        assert_eq!(round.outcome(), Some(Outcome::Push));
+        drop(round);
+        assert_eq!(
+            log.borrow().events().last(),
+            Some(&GameEvent::BalanceChanged {
+                player: 0,
+                balance: 100
+            })
+        );
+    }
+
+    // A table with the default player on seats 0 and 2 and Bob, with $50, on seat 1.
+    fn table_game(cards: &[&str]) -> Game {
+        let mut game = stacked_game(cards);
+        let bob = game.add_player("Bob", 50);
+        game.add_seat(bob).unwrap();
+        game.add_seat(0).unwrap();
+        game
+    }
+
+    #[test]
+    fn test_table_busts() {
+        // Seat 0 gets 10 and 6, seat 1 10 and 5, seat 2 9 and 7, and the dealer 7 up
+        // and 5 in the hole. Every hit busts, and the dealer would draw to 21.
+        let cards = [
+            "10H", "10S", "9H", "7D", "6D", "5C", "7C", "5D", "KH", "QC", "9D", "2S",
+        ];
+
+        // The dealer plays out against the one hand still standing.
+        let mut game = table_game(&cards);
+        let mut round = game.start_round();
+        for _ in 0..3 {
+            round.apply(Action::Bet(10)).unwrap();
+        }
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Hit).unwrap();
+        round.apply(Action::Hit).unwrap();
+        assert_eq!(round.apply(Action::Stand), Ok(RoundState::DealerTurn));
+        round.apply(Action::PlayDealer).unwrap();
+        assert_eq!(round.dealer().len(), 3);
+        drop(round);
+
+        // Once every hand has busted the dealer does not draw.
+        let mut game = table_game(&cards);
+        let mut round = game.start_round();
+        for _ in 0..3 {
+            round.apply(Action::Bet(10)).unwrap();
+        }
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Hit).unwrap();
+        round.apply(Action::Hit).unwrap();
+        assert_eq!(round.apply(Action::Hit), Ok(RoundState::Settled));
+        assert_eq!(round.dealer().len(), 2);
+        assert!(round
+            .settlements()
+            .iter()
+            .all(|settlement| settlement.outcome == Outcome::Lose));
+    }
+
+    #[test]
+    fn test_table_round() {
+        let log = Rc::new(RefCell::new(EventLog::new()));
+        // Seat 0 gets 10 and 6, seat 1 gets 9 and 9, seat 2 a natural, and the dealer
+        // 7 up and 10 in the hole.
+        let mut game = table_game(&["10H", "9S", "AC", "7D", "6D", "9C", "KD", "10S", "5C"]);
+        game.subscribe(Box::new(log.clone()));
+        let mut round = game.start_round();
+
+        // Every seat bets from its own player's bankroll.
+        round.apply(Action::Bet(10)).unwrap();
+        assert_eq!(round.state(), RoundState::Betting(1));
+        assert_eq!(round.player().name(), "Bob");
+        assert_eq!(round.legal_actions(), vec![Action::Bet(50), Action::SitOut]);
+        assert_eq!(
+            round.apply(Action::Bet(60)),
+            Err(ActionError::InvalidBet(BetError::ExceedsBalance))
+        );
+        round.apply(Action::Bet(20)).unwrap();
+        assert_eq!(round.apply(Action::Bet(10)), Ok(RoundState::Dealing));
+        assert_eq!(round.players()[0].balance(), 80);
+        assert_eq!(round.players()[1].balance(), 30);
+
+        // The seats act in order. The natural on seat 2 has nothing to decide.
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::PlayerTurn(0, 0)));
+        assert_eq!(round.apply(Action::Hit), Ok(RoundState::PlayerTurn(1, 0)));
+        assert_eq!(round.bet(), 20);
+        assert_eq!(round.apply(Action::Stand), Ok(RoundState::DealerTurn));
+
+        // Every seat is settled against the dealer's 17.
+        assert_eq!(round.apply(Action::PlayDealer), Ok(RoundState::Settled));
+        let results: Vec<(usize, Outcome, i32)> = round
+            .settlements()
+            .iter()
+            .map(|settlement| (settlement.seat, settlement.outcome, settlement.delta))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                (0, Outcome::Win, 10),
+                (1, Outcome::Win, 20),
+                (2, Outcome::Blackjack, 15),
+            ]
+        );
+        assert_eq!(round.players()[0].balance(), 125);
+        assert_eq!(round.players()[1].balance(), 70);
+        drop(round);
+
+        // One card to each seat, the upcard, a second card to each seat, then the hole
+        // card.
+        let dealt: Vec<Participant> = log
+            .borrow()
+            .events()
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::CardDealt { to, .. } => Some(*to),
+                GameEvent::HoleCardDealt => Some(Participant::Dealer),
+                _ => None,
+            })
+            .take(8)
+            .collect();
+        assert_eq!(
+            dealt,
+            vec![
+                Participant::Seat(0),
+                Participant::Seat(1),
+                Participant::Seat(2),
+                Participant::Dealer,
+                Participant::Seat(0),
+                Participant::Seat(1),
+                Participant::Seat(2),
+                Participant::Dealer,
+            ]
+        );
+        assert!(log.borrow().events().ends_with(&[
+            GameEvent::BalanceChanged {
+                player: 0,
+                balance: 125,
+            },
+            GameEvent::BalanceChanged {
+                player: 1,
+                balance: 70,
+            },
+        ]));
+    }
+
+    #[test]
+    fn test_table_sit_out() {
+        // Bob sits out, so seat 1 is skipped when dealing and playing.
+        let mut game = table_game(&["10H", "9S", "7D", "6D", "9C", "10S"]);
+        let mut round = game.start_round();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::SitOut).unwrap();
+        round.apply(Action::Bet(10)).unwrap();
+        round.apply(Action::Deal).unwrap();
+        assert!(!round.seats()[1].in_play());
+        assert_eq!(round.seats()[2].hands()[0].hand.value().total, 18);
+        assert_eq!(round.apply(Action::Stand), Ok(RoundState::PlayerTurn(2, 0)));
+        assert_eq!(round.apply(Action::Stand), Ok(RoundState::DealerTurn));
+        round.apply(Action::PlayDealer).unwrap();
+        assert_eq!(round.settlements().len(), 2);
+        assert_eq!(round.players()[1].balance(), 50);
+        drop(round);
+
+        // With every seat sitting out there is nothing to deal.
+        let mut round = game.start_round();
+        for _ in 0..3 {
+            round.apply(Action::SitOut).unwrap();
+        }
+        assert_eq!(round.state(), RoundState::Settled);
+        assert!(round.settlements().is_empty());
+        drop(round);
+
+        // Bob can't cover a $60 minimum, so sitting out is all they are offered.
+        game.set_rules(Rules {
+            min_bet: 60,
+            ..Rules::default()
+        });
+        let mut round = game.start_round();
+        round.apply(Action::Bet(60)).unwrap();
+        assert_eq!(round.legal_actions(), vec![Action::SitOut]);
+        assert_eq!(
+            round.apply(Action::Bet(50)),
+            Err(ActionError::InvalidBet(BetError::BelowMinimum(60)))
+        );
+        assert_eq!(round.apply(Action::SitOut), Ok(RoundState::Betting(2)));
+    }
+
+    #[test]
+    fn test_table_insurance() {
+        // The dealer shows an Ace over a blackjack. Seat 0 holds 19 and takes insurance,
+        // seat 1 has a natural and takes even money, seat 2 declines.
+        let mut game = table_game(&["10H", "AD", "10C", "AC", "9D", "KS", "8C", "KH"]);
+        let mut round = game.start_round();
+        for bet in [10, 10, 10] {
+            round.apply(Action::Bet(bet)).unwrap();
+        }
+        assert_eq!(round.apply(Action::Deal), Ok(RoundState::Insurance(0)));
+        assert_eq!(
+            round.apply(Action::Insurance(5)),
+            Ok(RoundState::Insurance(1))
+        );
+        assert_eq!(
+            round.legal_actions(),
+            vec![Action::EvenMoney, Action::DeclineInsurance]
+        );
+        assert_eq!(round.apply(Action::EvenMoney), Ok(RoundState::Insurance(2)));
+        assert_eq!(round.insurance(), 0);
+
+        // The dealer checks once everyone has answered and the blackjack ends the round.
+        assert_eq!(
+            round.apply(Action::DeclineInsurance),
+            Ok(RoundState::Settled)
+        );
+        let results: Vec<(usize, Outcome)> = round
+            .settlements()
+            .iter()
+            .map(|settlement| (settlement.seat, settlement.outcome))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                (0, Outcome::Lose),
+                (0, Outcome::InsuranceWin),
+                (1, Outcome::Win),
+                (2, Outcome::Lose),
+            ]
+        );
+        // The player broke even on seat 0 and lost seat 2.
+        assert_eq!(round.players()[0].balance(), 90);
+        assert_eq!(round.players()[1].balance(), 60);
+    }
+
+    #[test]
+    fn test_table_round_dropped() {
+        let mut game = table_game(&["10H", "AD", "10C", "AC", "9D", "KS", "8C", "5H"]);
+        let mut round = game.start_round();
+        for bet in [10, 20, 30] {
+            round.apply(Action::Bet(bet)).unwrap();
+        }
+        round.apply(Action::Deal).unwrap();
+        round.apply(Action::Insurance(5)).unwrap();
+        drop(round);
+
+        // Every seat's bets go back to the player who placed them.
+        assert_eq!(game.players()[0].balance(), 100);
+        assert_eq!(game.players()[1].balance(), 50);
+    }
+}
@@ -0,0 +1,567 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bankroll::BetError;
+
+/// The most hands a seat can be split into, seven splits. It is as far as the
+/// notation goes, so rules within it always print as notation that parses back.
+pub const MAX_SPLIT_HANDS: usize = 8;
+
+/// What a natural blackjack pays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum BlackjackPayout {
+    // 3 to 2, the traditional payout.
+    #[default]
+    #[serde(rename = "3:2")]
+    ThreeToTwo,
+    // 6 to 5, common on single-deck and low-limit tables.
+    #[serde(rename = "6:5")]
+    SixToFive,
+    // 1 to 1, the same as any other win.
+    #[serde(rename = "1:1")]
+    EvenMoney,
+}
+
+impl BlackjackPayout {
+    /// The winnings on a natural for the given bet, rounded down to a whole dollar and
+    /// capped at `i32::MAX`.
+    pub fn pay(self, bet: i32) -> i32 {
+        // Worked out in i64 so that large bets don't overflow on the way.
+        let bet = i64::from(bet);
+        let win = match self {
+            BlackjackPayout::ThreeToTwo => bet * 3 / 2,
+            BlackjackPayout::SixToFive => bet * 6 / 5,
+            BlackjackPayout::EvenMoney => bet,
+        };
+        i32::try_from(win).unwrap_or(i32::MAX)
+    }
+}
+
+impl fmt::Display for BlackjackPayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ratio = match self {
+            BlackjackPayout::ThreeToTwo => "3:2",
+            BlackjackPayout::SixToFive => "6:5",
+            BlackjackPayout::EvenMoney => "1:1",
+        };
+        f.write_str(ratio)
+    }
+}
+
+/// When a player may give up their hand for half of the bet back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Surrender {
+    // Hands can't be surrendered.
+    #[default]
+    #[serde(rename = "none")]
+    NotOffered,
+    // Only once the dealer has checked for blackjack, so a dealer natural still wins
+    // the whole bet.
+    Late,
+    // Before the dealer checks for blackjack.
+    Early,
+}
+
+/// How the dealer's second card is dealt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HoleCard {
+    // Dealt face down with the opening cards. The dealer checks it for blackjack under
+    // an Ace or a ten, so a dealer blackjack only takes the original bet.
+    #[default]
+    Peek,
+    // Not dealt until the players have finished, as in European games. A dealer
+    // blackjack takes every bet, including doubles and splits.
+    NoHoleCard,
+}
+
+/// The table rules a game is played under.
+///
+/// In a config file every field is optional and falls back to its default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    // The number of decks in the shoe.
+    pub decks: usize,
+    // How much of the shoe is dealt before the cut card comes out, in percent.
+    pub penetration_percent: u8,
+    // Whether the dealer hits a soft 17 (H17) rather than standing on it (S17).
+    pub dealer_hits_soft_17: bool,
+    // Whether the dealer takes a hole card and peeks at it.
+    pub hole_card: HoleCard,
+    // What a natural blackjack pays.
+    pub blackjack_payout: BlackjackPayout,
+    // Whether a hand made by splitting can be doubled.
+    pub double_after_split: bool,
+    // The most hands a seat can hold through splitting and re-splitting, from 1 (no
+    // splitting) to `MAX_SPLIT_HANDS`.
+    pub max_split_hands: usize,
+    // Whether a pair of Aces made by splitting Aces can be split again.
+    pub resplit_aces: bool,
+    // Whether split Aces can be hit. Most tables deal them one card each.
+    pub hit_split_aces: bool,
+    // Whether an Ace and a ten on a split hand counts as a blackjack rather than 21.
+    pub blackjack_after_split: bool,
+    // Whether, and when, the first two cards can be surrendered.
+    pub surrender: Surrender,
+    // The smallest bet the table takes.
+    pub min_bet: i32,
+    // The largest bet the table takes, if there is a limit.
+    pub max_bet: Option<i32>,
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules {
+            decks: 6,
+            penetration_percent: 75,
+            dealer_hits_soft_17: false,
+            hole_card: HoleCard::Peek,
+            blackjack_payout: BlackjackPayout::ThreeToTwo,
+            double_after_split: true,
+            max_split_hands: 4,
+            resplit_aces: false,
+            hit_split_aces: false,
+            blackjack_after_split: false,
+            surrender: Surrender::NotOffered,
+            min_bet: 1,
+            max_bet: None,
+        }
+    }
+}
+
+impl Rules {
+    /// Las Vegas Strip rules: four decks, the dealer stands on soft 17 and late
+    /// surrender is offered.
+    pub fn vegas_strip() -> Rules {
+        Rules {
+            decks: 4,
+            surrender: Surrender::Late,
+            ..Rules::default()
+        }
+    }
+
+    /// Atlantic City rules: eight decks, the dealer stands on soft 17 and late
+    /// surrender is offered.
+    pub fn atlantic_city() -> Rules {
+        Rules {
+            decks: 8,
+            surrender: Surrender::Late,
+            ..Rules::default()
+        }
+    }
+
+    /// European rules: no hole card, no doubling after a split, a pair can be split
+    /// only once and there is no surrender.
+    pub fn european() -> Rules {
+        Rules {
+            hole_card: HoleCard::NoHoleCard,
+            double_after_split: false,
+            max_split_hands: 2,
+            ..Rules::default()
+        }
+    }
+
+    /// The share of the shoe dealt before a reshuffle, as `Shoe` takes it.
+    pub fn penetration(&self) -> f64 {
+        f64::from(self.penetration_percent) / 100.0
+    }
+
+    /// Checks a bet against the table minimum and maximum.
+    pub fn check_bet(&self, bet: i32) -> Result<(), BetError> {
+        if bet < self.min_bet {
+            return Err(BetError::BelowMinimum(self.min_bet));
+        }
+        match self.max_bet {
+            Some(max) if bet > max => Err(BetError::AboveMaximum(max)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Why a token of a rule notation was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleTokenError {
+    // Not a rule the notation knows.
+    Unknown,
+    // Sets a rule that an earlier token already set.
+    Repeated,
+    // A number the game can't be played with.
+    OutOfRange,
+}
+
+/// Why a rule notation such as `6D H17 DAS 3:2 75%` could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRulesError {
+    // The offending token.
+    pub token: String,
+    // Where the token starts in the notation, counting characters from 1.
+    pub column: usize,
+    pub kind: RuleTokenError,
+}
+
+impl fmt::Display for ParseRulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problem = match self.kind {
+            RuleTokenError::Unknown => "is not a known rule",
+            RuleTokenError::Repeated => "repeats or contradicts an earlier rule",
+            RuleTokenError::OutOfRange => "is out of range",
+        };
+        write!(f, "'{}' at column {} {}", self.token, self.column, problem)
+    }
+}
+
+impl std::error::Error for ParseRulesError {}
+
+// Writes the rules in the compact notation read by `from_str`, e.g.
+// `6D H17 DAS RSA LS 3:2 75%`. Rules at their default that are usually left unsaid,
+// such as a peeking dealer or no surrender, are left out.
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let soft_17 = if self.dealer_hits_soft_17 {
+            "H17"
+        } else {
+            "S17"
+        };
+        let das = if self.double_after_split {
+            "DAS"
+        } else {
+            "NDAS"
+        };
+        write!(f, "{}D {} {}", self.decks, soft_17, das)?;
+        if self.max_split_hands != Rules::default().max_split_hands {
+            write!(f, " SPL{}", self.max_split_hands.saturating_sub(1))?;
+        }
+        if self.resplit_aces {
+            write!(f, " RSA")?;
+        }
+        if self.hit_split_aces {
+            write!(f, " HSA")?;
+        }
+        if self.blackjack_after_split {
+            write!(f, " BJAS")?;
+        }
+        match self.surrender {
+            Surrender::NotOffered => {}
+            Surrender::Late => write!(f, " LS")?,
+            Surrender::Early => write!(f, " ES")?,
+        }
+        if self.hole_card == HoleCard::NoHoleCard {
+            write!(f, " ENHC")?;
+        }
+        write!(
+            f,
+            " {} {}%",
+            self.blackjack_payout, self.penetration_percent
+        )?;
+        if self.min_bet != 1 || self.max_bet.is_some() {
+            write!(f, " ${}", self.min_bet)?;
+            if let Some(max) = self.max_bet {
+                write!(f, "-{}", max)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the compact rule notation, e.g. `6D H17 DAS RSA LS 3:2 75%`.
+///
+/// Tokens are separated by spaces, can come in any order and are not case sensitive.
+/// Anything left out keeps its default.
+///
+/// | Token | Rule |
+/// |---|---|
+/// | `6D` | decks in the shoe, 1 to 8 |
+/// | `H17` / `S17` | the dealer hits / stands on soft 17 |
+/// | `DAS` / `NDAS` | doubling after a split is allowed / not allowed |
+/// | `SPL3` | a pair can be split up to 3 times, making 4 hands; `SPL0` to `SPL7` |
+/// | `RSA`, `HSA` | split Aces can be re-split, can be hit |
+/// | `BJAS` | an Ace and a ten after a split counts as a blackjack |
+/// | `LS` / `ES` | late / early surrender |
+/// | `ENHC` | no hole card |
+/// | `3:2`, `6:5`, `1:1` | the blackjack payout |
+/// | `75%` | the penetration |
+/// | `$5`, `$5-500` | the table minimum, and maximum |
+impl FromStr for Rules {
+    type Err = ParseRulesError;
+
+    fn from_str(notation: &str) -> Result<Rules, ParseRulesError> {
+        let mut rules = Rules::default();
+        // The settings given so far, to catch repeats such as `H17 S17`.
+        let mut seen = HashSet::new();
+
+        for token in notation.split_whitespace() {
+            let offset = token.as_ptr() as usize - notation.as_ptr() as usize;
+            let error = |kind| ParseRulesError {
+                token: token.to_string(),
+                column: notation[..offset].chars().count() + 1,
+                kind,
+            };
+
+            let upper = token.to_ascii_uppercase();
+            let setting = match upper.as_str() {
+                "H17" | "S17" => {
+                    rules.dealer_hits_soft_17 = upper == "H17";
+                    "soft 17"
+                }
+                "DAS" | "NDAS" => {
+                    rules.double_after_split = upper == "DAS";
+                    "double after split"
+                }
+                "RSA" => {
+                    rules.resplit_aces = true;
+                    "resplit aces"
+                }
+                "HSA" => {
+                    rules.hit_split_aces = true;
+                    "hit split aces"
+                }
+                "BJAS" => {
+                    rules.blackjack_after_split = true;
+                    "blackjack after split"
+                }
+                "LS" | "ES" => {
+                    rules.surrender = if upper == "LS" {
+                        Surrender::Late
+                    } else {
+                        Surrender::Early
+                    };
+                    "surrender"
+                }
+                "ENHC" => {
+                    rules.hole_card = HoleCard::NoHoleCard;
+                    "hole card"
+                }
+                "3:2" | "6:5" | "1:1" => {
+                    rules.blackjack_payout = match upper.as_str() {
+                        "3:2" => BlackjackPayout::ThreeToTwo,
+                        "6:5" => BlackjackPayout::SixToFive,
+                        _ => BlackjackPayout::EvenMoney,
+                    };
+                    "payout"
+                }
+                _ => {
+                    if let Some(decks) = upper.strip_suffix('D') {
+                        let decks: usize =
+                            decks.parse().map_err(|_| error(RuleTokenError::Unknown))?;
+                        if !(1..=8).contains(&decks) {
+                            return Err(error(RuleTokenError::OutOfRange));
+                        }
+                        rules.decks = decks;
+                        "decks"
+                    } else if let Some(splits) = upper.strip_prefix("SPL") {
+                        let splits: usize =
+                            splits.parse().map_err(|_| error(RuleTokenError::Unknown))?;
+                        if splits >= MAX_SPLIT_HANDS {
+                            return Err(error(RuleTokenError::OutOfRange));
+                        }
+                        rules.max_split_hands = splits + 1;
+                        "splits"
+                    } else if let Some(percent) = upper.strip_suffix('%') {
+                        let percent: u32 = percent
+                            .parse()
+                            .map_err(|_| error(RuleTokenError::Unknown))?;
+                        if !(1..=100).contains(&percent) {
+                            return Err(error(RuleTokenError::OutOfRange));
+                        }
+                        rules.penetration_percent = percent as u8;
+                        "penetration"
+                    } else if let Some(limits) = upper.strip_prefix('$') {
+                        let (min, max) = match limits.split_once('-') {
+                            Some((min, max)) => (min, Some(max)),
+                            None => (limits, None),
+                        };
+                        let amount = |text: &str| match text.parse::<i32>() {
+                            Ok(amount) if amount > 0 => Ok(amount),
+                            Ok(_) => Err(error(RuleTokenError::OutOfRange)),
+                            Err(_) => Err(error(RuleTokenError::Unknown)),
+                        };
+                        rules.min_bet = amount(min)?;
+                        rules.max_bet = max.map(amount).transpose()?;
+                        if rules.max_bet.is_some_and(|max| max < rules.min_bet) {
+                            return Err(error(RuleTokenError::OutOfRange));
+                        }
+                        "limits"
+                    } else {
+                        return Err(error(RuleTokenError::Unknown));
+                    }
+                }
+            };
+            if !seen.insert(setting) {
+                return Err(error(RuleTokenError::Repeated));
+            }
+        }
+        Ok(rules)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_blackjack_payout() {
+        assert_eq!(BlackjackPayout::ThreeToTwo.pay(10), 15);
+        assert_eq!(BlackjackPayout::SixToFive.pay(10), 12);
+        assert_eq!(BlackjackPayout::EvenMoney.pay(10), 10);
+
+        // Odd amounts are rounded down in the house's favour.
+        assert_eq!(BlackjackPayout::ThreeToTwo.pay(5), 7);
+        assert_eq!(BlackjackPayout::SixToFive.pay(7), 8);
+
+        // Large bets are paid in full, and what doesn't fit in an i32 is capped.
+        assert_eq!(
+            BlackjackPayout::ThreeToTwo.pay(1_000_000_000),
+            1_500_000_000
+        );
+        assert_eq!(BlackjackPayout::SixToFive.pay(1_000_000_000), 1_200_000_000);
+        assert_eq!(BlackjackPayout::ThreeToTwo.pay(i32::MAX), i32::MAX);
+        assert_eq!(BlackjackPayout::EvenMoney.pay(i32::MAX), i32::MAX);
+
+        assert_eq!(BlackjackPayout::default().to_string(), "3:2");
+    }
+
+    #[test]
+    fn test_presets() {
+        let vegas = Rules::vegas_strip();
+        assert_eq!((vegas.decks, vegas.surrender), (4, Surrender::Late));
+        assert!(!vegas.dealer_hits_soft_17);
+
+        let atlantic_city = Rules::atlantic_city();
+        assert_eq!(atlantic_city.decks, 8);
+        assert_eq!(atlantic_city.hole_card, HoleCard::Peek);
+
+        let european = Rules::european();
+        assert_eq!(european.hole_card, HoleCard::NoHoleCard);
+        assert_eq!(european.surrender, Surrender::NotOffered);
+        assert!(!european.double_after_split);
+    }
+
+    #[test]
+    fn test_parse_notation() {
+        let rules: Rules = "6D H17 DAS RSA LS 3:2 75%".parse().unwrap();
+        assert_eq!(
+            rules,
+            Rules {
+                decks: 6,
+                dealer_hits_soft_17: true,
+                double_after_split: true,
+                resplit_aces: true,
+                surrender: Surrender::Late,
+                blackjack_payout: BlackjackPayout::ThreeToTwo,
+                penetration_percent: 75,
+                ..Rules::default()
+            }
+        );
+
+        // Tokens can come in any order and in any case; the rest keeps its default.
+        let rules: Rules = "$10-500 enhc 2d 6:5 spl1 ndas".parse().unwrap();
+        assert_eq!(
+            rules,
+            Rules {
+                decks: 2,
+                hole_card: HoleCard::NoHoleCard,
+                blackjack_payout: BlackjackPayout::SixToFive,
+                max_split_hands: 2,
+                double_after_split: false,
+                min_bet: 10,
+                max_bet: Some(500),
+                ..Rules::default()
+            }
+        );
+        assert_eq!("".parse::<Rules>().unwrap(), Rules::default());
+    }
+
+    #[test]
+    fn test_notation_round_trip() {
+        assert_eq!(Rules::default().to_string(), "6D S17 DAS 3:2 75%");
+        assert_eq!(
+            Rules::european().to_string(),
+            "6D S17 NDAS SPL1 ENHC 3:2 75%"
+        );
+
+        let custom = Rules {
+            decks: 1,
+            penetration_percent: 60,
+            dealer_hits_soft_17: true,
+            hit_split_aces: true,
+            resplit_aces: true,
+            blackjack_after_split: true,
+            surrender: Surrender::Early,
+            blackjack_payout: BlackjackPayout::EvenMoney,
+            min_bet: 5,
+            ..Rules::default()
+        };
+        assert_eq!(custom.to_string(), "1D H17 DAS RSA HSA BJAS ES 1:1 60% $5");
+        let most_splits = Rules {
+            max_split_hands: MAX_SPLIT_HANDS,
+            ..Rules::default()
+        };
+        assert_eq!(most_splits.to_string(), "6D S17 DAS SPL7 3:2 75%");
+        for rules in [
+            Rules::default(),
+            Rules::vegas_strip(),
+            Rules::atlantic_city(),
+            Rules::european(),
+            custom,
+            most_splits,
+        ] {
+            assert_eq!(rules.to_string().parse::<Rules>(), Ok(rules));
+        }
+    }
+
+    #[test]
+    fn test_notation_errors() {
+        // (notation, offending token, column, kind)
+        let cases = [
+            ("6D H17 XYZ 3:2", "XYZ", 8, RuleTokenError::Unknown),
+            ("6D H17 DAS S17", "S17", 12, RuleTokenError::Repeated),
+            ("LS  ES", "ES", 5, RuleTokenError::Repeated),
+            ("0D", "0D", 1, RuleTokenError::OutOfRange),
+            ("6D 8D", "8D", 4, RuleTokenError::Repeated),
+            ("12D", "12D", 1, RuleTokenError::OutOfRange),
+            ("3:2 150%", "150%", 5, RuleTokenError::OutOfRange),
+            ("$500-10", "$500-10", 1, RuleTokenError::OutOfRange),
+            ("$0", "$0", 1, RuleTokenError::OutOfRange),
+            ("2:1", "2:1", 1, RuleTokenError::Unknown),
+            ("SPLIT", "SPLIT", 1, RuleTokenError::Unknown),
+            ("SPL8", "SPL8", 1, RuleTokenError::OutOfRange),
+            ("$ten", "$ten", 1, RuleTokenError::Unknown),
+        ];
+        for (notation, token, column, kind) in cases {
+            assert_eq!(
+                notation.parse::<Rules>(),
+                Err(ParseRulesError {
+                    token: token.to_string(),
+                    column,
+                    kind,
+                }),
+                "{}",
+                notation
+            );
+        }
+
+        let err = "6D H17 XYZ".parse::<Rules>().unwrap_err();
+        assert_eq!(err.to_string(), "'XYZ' at column 8 is not a known rule");
+    }
+
+    #[test]
+    fn test_table_limits() {
+        let rules = Rules {
+            min_bet: 5,
+            max_bet: Some(500),
+            ..Rules::default()
+        };
+        assert_eq!(rules.check_bet(5), Ok(()));
+        assert_eq!(rules.check_bet(500), Ok(()));
+        assert_eq!(rules.check_bet(4), Err(BetError::BelowMinimum(5)));
+        assert_eq!(rules.check_bet(501), Err(BetError::AboveMaximum(500)));
+
+        // Without a maximum any bet from the minimum up is fine.
+        assert_eq!(Rules::default().check_bet(1_000_000), Ok(()));
+    }
+}
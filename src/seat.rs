@@ -0,0 +1,146 @@
+use crate::card::Rank;
+use crate::hand::Hand;
+
+/// One hand played from a seat, with the money riding on it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeatHand {
+    pub hand: Hand,
+    pub wager: i32,
+    // Whether the hand was made by splitting a pair.
+    pub split: bool,
+    // Whether the player gave the hand up for half of the wager.
+    pub surrendered: bool,
+}
+
+impl SeatHand {
+    pub fn new(wager: i32) -> SeatHand {
+        SeatHand {
+            hand: Hand::new(),
+            wager,
+            split: false,
+            surrendered: false,
+        }
+    }
+
+    /// Whether the hand started as one of a pair of split Aces.
+    pub fn is_split_aces(&self) -> bool {
+        self.split && self.hand.cards().first().map(|card| card.rank) == Some(Rank::Ace)
+    }
+}
+
+/// A betting position at the table, played by one player. A seat starts each round
+/// with one hand and can hold several once pairs are split.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Seat {
+    // The index of the player who sits here.
+    player: usize,
+    hands: Vec<SeatHand>,
+}
+
+impl Seat {
+    pub fn new(player: usize) -> Seat {
+        Seat {
+            player,
+            hands: Vec::new(),
+        }
+    }
+
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    /// Whether the seat has a bet in the current round.
+    pub fn in_play(&self) -> bool {
+        !self.hands.is_empty()
+    }
+
+    pub fn hands(&self) -> &[SeatHand] {
+        &self.hands
+    }
+
+    pub fn hands_mut(&mut self) -> &mut [SeatHand] {
+        &mut self.hands
+    }
+
+    pub fn hand(&self, index: usize) -> Option<&SeatHand> {
+        self.hands.get(index)
+    }
+
+    pub fn hand_mut(&mut self, index: usize) -> Option<&mut SeatHand> {
+        self.hands.get_mut(index)
+    }
+
+    pub fn clear(&mut self) {
+        self.hands.clear();
+    }
+
+    /// Clears the last round's hands and starts a new hand with the given wager.
+    pub fn place_bet(&mut self, wager: i32) {
+        self.hands.clear();
+        self.hands.push(SeatHand::new(wager));
+    }
+
+    /// Splits the pair at `index` into two hands of one card each. The new hand goes
+    /// right after it and carries the same wager.
+    pub fn split(&mut self, index: usize) {
+        let seat_hand = &mut self.hands[index];
+        let card = seat_hand.hand.pop().expect("a pair has two cards");
+        seat_hand.split = true;
+
+        let mut new_hand = SeatHand::new(seat_hand.wager);
+        new_hand.hand.push(card);
+        new_hand.split = true;
+        self.hands.insert(index + 1, new_hand);
+    }
+
+    /// The money riding on every hand at the seat.
+    pub fn total_wager(&self) -> i32 {
+        self.hands.iter().map(|hand| hand.wager).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::{Card, Rank, Suit};
+
+    #[test]
+    fn test_place_bet() {
+        let mut seat = Seat::new(0);
+        assert!(seat.hands().is_empty());
+
+        seat.place_bet(25);
+        seat.hand_mut(0)
+            .unwrap()
+            .hand
+            .push(Card::new(Rank::Ace, Suit::Clubs));
+        assert_eq!(seat.total_wager(), 25);
+        assert_eq!(seat.hand(0).unwrap().hand.len(), 1);
+
+        // A new bet starts over with a single empty hand.
+        seat.place_bet(10);
+        assert_eq!(seat.hands(), &[SeatHand::new(10)]);
+        assert!(seat.hand(1).is_none());
+    }
+
+    #[test]
+    fn test_split() {
+        let mut seat = Seat::new(0);
+        seat.place_bet(10);
+        let eight = Card::new(Rank::Eight, Suit::Clubs);
+        let other_eight = Card::new(Rank::Eight, Suit::Hearts);
+        seat.hand_mut(0).unwrap().hand.push(eight);
+        seat.hand_mut(0).unwrap().hand.push(other_eight);
+
+        seat.split(0);
+        assert_eq!(seat.hands().len(), 2);
+        assert_eq!(seat.hand(0).unwrap().hand.cards(), &[eight]);
+        assert_eq!(seat.hand(1).unwrap().hand.cards(), &[other_eight]);
+        assert!(seat
+            .hands()
+            .iter()
+            .all(|hand| hand.split && hand.wager == 10));
+        assert_eq!(seat.total_wager(), 20);
+        assert!(!seat.hand(0).unwrap().is_split_aces());
+    }
+}
@@ -0,0 +1,278 @@
+use crate::dealer::DealerHand;
+use crate::rules::Rules;
+use crate::seat::SeatHand;
+
+/// How a wager was settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    // The hand beat the dealer and is paid 1:1.
+    Win,
+    // The hand busted or lost to the dealer.
+    Lose,
+    // The hand tied the dealer and the wager is returned.
+    Push,
+    // A natural that beat the dealer, paid at the table's blackjack payout.
+    Blackjack,
+    // The player gave up the hand for half of the wager back.
+    Surrender,
+    // The dealer had a blackjack and the insurance bet is paid 2:1.
+    InsuranceWin,
+    // The dealer had no blackjack and the insurance bet is lost.
+    InsuranceLose,
+}
+
+/// The result of settling one wager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settlement {
+    pub outcome: Outcome,
+    // The money that was riding, already taken from the bankroll when it was placed.
+    pub wager: i32,
+    // How much the player won (positive) or lost (negative) on the wager.
+    pub delta: i32,
+    // The seat the wager was placed on. The settle functions leave it at 0 and a round
+    // fills it in.
+    pub seat: usize,
+}
+
+impl Settlement {
+    fn new(outcome: Outcome, wager: i32, delta: i32) -> Settlement {
+        Settlement {
+            outcome,
+            wager,
+            delta,
+            seat: 0,
+        }
+    }
+
+    /// The amount handed back to the bankroll: the escrowed wager plus or minus the
+    /// result, capped at `i32::MAX`.
+    pub fn payout(&self) -> i32 {
+        self.wager.saturating_add(self.delta)
+    }
+}
+
+/// Settles a finished hand against the dealer's final hand.
+pub fn settle(seat_hand: &SeatHand, dealer: &DealerHand, rules: &Rules) -> Settlement {
+    let wager = seat_hand.wager;
+    if seat_hand.surrendered {
+        return settle_surrender(wager);
+    }
+    let mut player = seat_hand.hand.value();
+    let dealer = dealer.value();
+
+    // An Ace and a ten on a split hand is only 21, unless the table says otherwise.
+    if seat_hand.split && !rules.blackjack_after_split {
+        player.is_blackjack = false;
+    }
+
+    let outcome = if player.is_bust {
+        // A busted hand loses even if the dealer busts too.
+        Outcome::Lose
+    } else if player.is_blackjack || dealer.is_blackjack {
+        // A natural only ties another natural; it beats any other 21 and loses to one.
+        match (player.is_blackjack, dealer.is_blackjack) {
+            (true, true) => Outcome::Push,
+            (true, false) => Outcome::Blackjack,
+            _ => Outcome::Lose,
+        }
+    } else if dealer.is_bust || player.total > dealer.total {
+        Outcome::Win
+    } else if player.total == dealer.total {
+        Outcome::Push
+    } else {
+        Outcome::Lose
+    };
+
+    let delta = match outcome {
+        Outcome::Win => wager,
+        Outcome::Blackjack => rules.blackjack_payout.pay(wager),
+        Outcome::Push => 0,
+        _ => -wager,
+    };
+    Settlement::new(outcome, wager, delta)
+}
+
+/// Settles a surrendered hand: half of the wager, rounded down, is handed back.
+pub fn settle_surrender(wager: i32) -> Settlement {
+    Settlement::new(Outcome::Surrender, wager, -(wager - wager / 2))
+}
+
+/// Settles a natural the player took even money for: paid 1:1 whatever the dealer
+/// holds.
+pub fn settle_even_money(wager: i32) -> Settlement {
+    Settlement::new(Outcome::Win, wager, wager)
+}
+
+/// Settles an insurance bet, which pays 2:1 if the dealer has a blackjack.
+pub fn settle_insurance(stake: i32, dealer: &DealerHand) -> Settlement {
+    if dealer.value().is_blackjack {
+        Settlement::new(Outcome::InsuranceWin, stake, stake * 2)
+    } else {
+        Settlement::new(Outcome::InsuranceLose, stake, -stake)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::Card;
+    use crate::hand::Hand;
+    use crate::rules::BlackjackPayout;
+
+    // Cards written as short names separated by spaces, e.g. "AH KS".
+    fn cards(names: &str) -> Vec<Card> {
+        names
+            .split_whitespace()
+            .map(|name| name.parse().unwrap())
+            .collect()
+    }
+
+    fn seat_hand(names: &str, split: bool) -> SeatHand {
+        SeatHand {
+            hand: Hand::from(cards(names)),
+            wager: 10,
+            split,
+            surrendered: false,
+        }
+    }
+
+    #[test]
+    fn test_settle() {
+        // (player, dealer, outcome, delta) on a $10 wager at a 3:2 table.
+        let cases: &[(&str, &str, Outcome, i32)] = &[
+            // Higher total wins 1:1, lower total loses and equal totals push.
+            ("10H 8S", "10D 7C", Outcome::Win, 10),
+            ("10H 8S", "10D KC", Outcome::Lose, -10),
+            ("10H 7S", "10D 7C", Outcome::Push, 0),
+            // A player bust loses to a standing dealer...
+            ("10H 10D 10C", "10S 7C", Outcome::Lose, -10),
+            // ...and to a busted dealer, even on the same total.
+            ("10H 5D 7C", "10S 6C 6D", Outcome::Lose, -10),
+            // A dealer bust pays any standing hand.
+            ("10D 2C", "8H 8D 10H", Outcome::Win, 10),
+            // A natural is paid 3:2, even against a dealer's three-card 21.
+            ("AH KS", "10D 7C", Outcome::Blackjack, 15),
+            ("AH KS", "7D 7C 7H", Outcome::Blackjack, 15),
+            // Natural against natural is a push.
+            ("AH KS", "AD QC", Outcome::Push, 0),
+            // A dealer natural beats a three-card 21.
+            ("7D 7C 7H", "AD QC", Outcome::Lose, -10),
+            // Two three-card 21s push.
+            ("7D 7C 7H", "5D 6C KH", Outcome::Push, 0),
+        ];
+
+        for (player, dealer, outcome, delta) in cases {
+            let dealer_hand = DealerHand::from(cards(dealer));
+            let settlement = settle(&seat_hand(player, false), &dealer_hand, &Rules::default());
+            assert_eq!(
+                (settlement.outcome, settlement.delta),
+                (*outcome, *delta),
+                "{} against {}",
+                player,
+                dealer
+            );
+            assert_eq!(settlement.payout(), 10 + delta);
+        }
+    }
+
+    #[test]
+    fn test_settle_blackjack_payouts() {
+        let hand = seat_hand("AH KS", false);
+        let dealer = DealerHand::from(cards("10D 7C"));
+        // (payout, delta) on a $10 wager.
+        for (payout, delta) in [
+            (BlackjackPayout::ThreeToTwo, 15),
+            (BlackjackPayout::SixToFive, 12),
+            (BlackjackPayout::EvenMoney, 10),
+        ] {
+            let rules = Rules {
+                blackjack_payout: payout,
+                ..Rules::default()
+            };
+            let settlement = settle(&hand, &dealer, &rules);
+            assert_eq!(settlement.outcome, Outcome::Blackjack);
+            assert_eq!(settlement.delta, delta);
+        }
+
+        // A bet near the top of the range is paid without overflowing.
+        let hand = SeatHand {
+            wager: 2_000_000_000,
+            ..hand
+        };
+        let settlement = settle(&hand, &dealer, &Rules::default());
+        assert_eq!(settlement.delta, i32::MAX);
+        assert_eq!(settlement.payout(), i32::MAX);
+    }
+
+    #[test]
+    fn test_settle_split_hands() {
+        let rules = Rules::default();
+        let blackjack_after_split = Rules {
+            blackjack_after_split: true,
+            ..Rules::default()
+        };
+        // (player, dealer, rules, outcome, delta) for split hands on a $10 wager.
+        let cases: &[(&str, &str, &Rules, Outcome, i32)] = &[
+            // An Ace and a ten after a split is an ordinary 21...
+            ("AH KS", "10D 7C", &rules, Outcome::Win, 10),
+            ("AH KS", "7D 7C 7H", &rules, Outcome::Push, 0),
+            ("AH KS", "AD QC", &rules, Outcome::Lose, -10),
+            // ...unless the table pays it as a blackjack.
+            (
+                "AH KS",
+                "10D 7C",
+                &blackjack_after_split,
+                Outcome::Blackjack,
+                15,
+            ),
+        ];
+        for (player, dealer, rules, outcome, delta) in cases {
+            let settlement = settle(
+                &seat_hand(player, true),
+                &DealerHand::from(cards(dealer)),
+                rules,
+            );
+            assert_eq!(
+                (settlement.outcome, settlement.delta),
+                (*outcome, *delta),
+                "{} against {}",
+                player,
+                dealer
+            );
+        }
+    }
+
+    #[test]
+    fn test_settle_surrender() {
+        // (wager, delta, payout)
+        for (wager, delta, payout) in [(10, -5, 5), (25, -13, 12), (1, -1, 0)] {
+            let settlement = settle_surrender(wager);
+            assert_eq!(settlement.outcome, Outcome::Surrender);
+            assert_eq!(settlement.delta, delta);
+            assert_eq!(settlement.payout(), payout);
+        }
+
+        // A surrendered hand loses half whatever the dealer holds.
+        let mut hand = seat_hand("10H 6S", false);
+        hand.surrendered = true;
+        let settlement = settle(&hand, &DealerHand::from(cards("AD QC")), &Rules::default());
+        assert_eq!(settlement, settle_surrender(10));
+    }
+
+    #[test]
+    fn test_settle_insurance() {
+        // (dealer cards, outcome, delta, payout) on a $5 insurance bet.
+        let cases: &[(&str, Outcome, i32, i32)] = &[
+            ("AH KS", Outcome::InsuranceWin, 10, 15),
+            ("AH 9S", Outcome::InsuranceLose, -5, 0),
+            // Three cards to 21 is not a blackjack.
+            ("AH 5S 5C", Outcome::InsuranceLose, -5, 0),
+        ];
+        for (dealer, outcome, delta, payout) in cases {
+            let settlement = settle_insurance(5, &DealerHand::from(cards(dealer)));
+            assert_eq!(settlement.outcome, *outcome);
+            assert_eq!(settlement.delta, *delta);
+            assert_eq!(settlement.payout(), *payout);
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+
+use crate::card::{new_deck, Card};
+
+/// The shoe the dealer deals from: several decks shuffled together, a cut card and a
+/// discard tray for the cards of finished rounds.
+///
+/// The shoe owns the random number generator used for every shuffle, so a shoe built
+/// from a seed deals the same cards every time.
+pub struct Shoe {
+    // Cards still to be dealt; the next card is at the end.
+    cards: Vec<Card>,
+    discards: Vec<Card>,
+    decks: usize,
+    penetration: f64,
+    // How many cards are left in the shoe when the cut card comes out.
+    cut_card: usize,
+    cut_card_out: bool,
+    rng: Box<dyn RngCore>,
+}
+
+impl Shoe {
+    /// Creates a shuffled shoe from the given number of decks. The cut card is placed so
+    /// that `penetration` (between 0 and 1) of the shoe is dealt before a reshuffle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decks` is zero or `penetration` is not in `0.0..=1.0`.
+    pub fn new(decks: usize, penetration: f64) -> Shoe {
+        Shoe::with_rng(decks, penetration, StdRng::from_entropy())
+    }
+
+    /// Creates a shoe whose shuffles are all determined by `seed`.
+    pub fn seeded(decks: usize, penetration: f64, seed: u64) -> Shoe {
+        Shoe::with_rng(decks, penetration, StdRng::seed_from_u64(seed))
+    }
+
+    /// Creates a shoe that shuffles with the given random number generator.
+    pub fn with_rng(decks: usize, penetration: f64, rng: impl RngCore + 'static) -> Shoe {
+        assert!(decks > 0, "a shoe needs at least one deck");
+        assert!(
+            (0.0..=1.0).contains(&penetration),
+            "penetration must be between 0 and 1"
+        );
+        let mut shoe = Shoe {
+            cards: Vec::new(),
+            discards: Vec::new(),
+            decks,
+            penetration,
+            cut_card: 0,
+            cut_card_out: false,
+            rng: Box::new(rng),
+        };
+        for _ in 0..decks {
+            shoe.discards.extend(new_deck());
+        }
+        shoe.shuffle();
+        shoe
+    }
+
+    pub fn decks(&self) -> usize {
+        self.decks
+    }
+
+    pub fn penetration(&self) -> f64 {
+        self.penetration
+    }
+
+    /// The number of cards left to deal.
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// The number of cards in the discard tray.
+    pub fn discarded(&self) -> usize {
+        self.discards.len()
+    }
+
+    /// Whether the cut card has come out, so the shoe should be shuffled before the
+    /// next round.
+    pub fn needs_shuffle(&self) -> bool {
+        self.cut_card_out
+    }
+
+    /// Deals the next card.
+    ///
+    /// A shoe dealt all the way to the end in the middle of a round is refilled from
+    /// the discard tray; `None` means there is nothing left to shuffle either.
+    pub fn draw(&mut self) -> Option<Card> {
+        if self.cards.is_empty() {
+            self.shuffle();
+        }
+        let card = self.cards.pop()?;
+        if self.cards.len() <= self.cut_card {
+            self.cut_card_out = true;
+        }
+        Some(card)
+    }
+
+    /// Puts the cards of a finished hand into the discard tray.
+    pub fn discard(&mut self, cards: &[Card]) {
+        self.discards.extend_from_slice(cards);
+    }
+
+    /// Empties the shoe and the discard tray and fills it with a fresh shuffle of the
+    /// given number of decks, keeping the random number generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decks` is zero or `penetration` is not in `0.0..=1.0`.
+    pub fn rebuild(&mut self, decks: usize, penetration: f64) {
+        assert!(decks > 0, "a shoe needs at least one deck");
+        assert!(
+            (0.0..=1.0).contains(&penetration),
+            "penetration must be between 0 and 1"
+        );
+        self.cards.clear();
+        self.discards.clear();
+        self.decks = decks;
+        self.penetration = penetration;
+        for _ in 0..decks {
+            self.discards.extend(new_deck());
+        }
+        self.shuffle();
+    }
+
+    /// Moves the discard tray back into the shoe, shuffles it and places the cut card.
+    pub fn shuffle(&mut self) {
+        self.cards.append(&mut self.discards);
+        self.cards.shuffle(&mut self.rng);
+
+        let dealt = (self.cards.len() as f64 * self.penetration).round() as usize;
+        self.cut_card = self.cards.len() - dealt.min(self.cards.len());
+        self.cut_card_out = false;
+    }
+}
+
+impl Default for Shoe {
+    /// A six-deck shoe with the cut card three quarters of the way in.
+    fn default() -> Shoe {
+        Shoe::new(6, 0.75)
+    }
+}
+
+// A stacked shoe that deals the given cards in order, with no cut card. Useful for
+// tests and for replaying a hand.
+impl From<Vec<Card>> for Shoe {
+    fn from(mut cards: Vec<Card>) -> Shoe {
+        cards.reverse();
+        Shoe {
+            cards,
+            discards: Vec::new(),
+            decks: 1,
+            penetration: 1.0,
+            cut_card: 0,
+            cut_card_out: false,
+            rng: Box::new(StdRng::from_entropy()),
+        }
+    }
+}
+
+// A stacked shoe from short card names such as "10H", for tests.
+#[cfg(test)]
+pub(crate) fn stacked(cards: &[&str]) -> Shoe {
+    let cards: Vec<Card> = cards.iter().map(|card| card.parse().unwrap()).collect();
+    Shoe::from(cards)
+}
+
+impl fmt::Debug for Shoe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shoe")
+            .field("decks", &self.decks)
+            .field("penetration", &self.penetration)
+            .field("remaining", &self.cards.len())
+            .field("discarded", &self.discards.len())
+            .field("cut_card_out", &self.cut_card_out)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::{Rank, Suit};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_shoe_sizes() {
+        for decks in [1, 2, 6, 8] {
+            let mut shoe = Shoe::new(decks, 0.75);
+            assert_eq!(shoe.remaining(), 52 * decks);
+
+            // Every card appears once per deck.
+            let mut counts: HashMap<Card, usize> = HashMap::new();
+            while shoe.remaining() > 0 {
+                *counts.entry(shoe.draw().unwrap()).or_default() += 1;
+            }
+            assert_eq!(counts.len(), 52);
+            assert!(counts.values().all(|&count| count == decks));
+        }
+    }
+
+    #[test]
+    fn test_cut_card() {
+        // Half of a two-deck shoe is dealt before the cut card comes out.
+        let mut shoe = Shoe::new(2, 0.5);
+        for _ in 0..51 {
+            shoe.draw().unwrap();
+        }
+        assert!(!shoe.needs_shuffle());
+        shoe.draw().unwrap();
+        assert!(shoe.needs_shuffle());
+
+        // Dealing goes on past the cut card until the shoe is shuffled.
+        shoe.draw().unwrap();
+        assert_eq!(shoe.remaining(), 51);
+    }
+
+    #[test]
+    fn test_discard_and_shuffle() {
+        let mut shoe = Shoe::new(1, 0.75);
+        let hand: Vec<Card> = (0..10).map(|_| shoe.draw().unwrap()).collect();
+        shoe.discard(&hand);
+        assert_eq!(shoe.remaining(), 42);
+        assert_eq!(shoe.discarded(), 10);
+
+        // Shuffling brings the discards back into the shoe.
+        shoe.shuffle();
+        assert_eq!(shoe.remaining(), 52);
+        assert_eq!(shoe.discarded(), 0);
+        assert!(!shoe.needs_shuffle());
+    }
+
+    #[test]
+    fn test_seeded_shoe() {
+        let deal =
+            |shoe: &mut Shoe| -> Vec<Card> { (0..20).map(|_| shoe.draw().unwrap()).collect() };
+
+        // The same seed deals the same cards, including after a reshuffle.
+        let mut first = Shoe::seeded(2, 0.75, 7);
+        let mut second = Shoe::seeded(2, 0.75, 7);
+        assert_eq!(deal(&mut first), deal(&mut second));
+        first.shuffle();
+        second.shuffle();
+        assert_eq!(deal(&mut first), deal(&mut second));
+
+        // Rebuilding keeps the generator, so it is as reproducible as the first shuffle.
+        first.rebuild(1, 0.5);
+        second.rebuild(1, 0.5);
+        assert_eq!(first.decks(), 1);
+        assert_eq!(first.remaining(), 52);
+        assert_eq!(first.discarded(), 0);
+        assert_eq!(deal(&mut first), deal(&mut second));
+
+        let mut other = Shoe::seeded(2, 0.75, 8);
+        assert_ne!(deal(&mut Shoe::seeded(2, 0.75, 7)), deal(&mut other));
+    }
+
+    #[test]
+    fn test_stacked_shoe() {
+        let ace = Card::new(Rank::Ace, Suit::Spades);
+        let king = Card::new(Rank::King, Suit::Hearts);
+        let mut shoe = Shoe::from(vec![ace, king]);
+        assert_eq!(shoe.draw(), Some(ace));
+        assert_eq!(shoe.draw(), Some(king));
+
+        // An empty shoe is refilled from the discard tray.
+        assert_eq!(shoe.draw(), None);
+        shoe.discard(&[ace]);
+        assert_eq!(shoe.draw(), Some(ace));
+    }
+}
@@ -0,0 +1,451 @@
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, Rank};
+use crate::hand::Hand;
+use crate::round::Action;
+
+/// How a computer-controlled player plays its hands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Strategy {
+    // The basic strategy chart, see `basic_strategy`.
+    #[default]
+    Basic,
+    // Plays like the dealer: hits below 17 and never doubles, splits or surrenders.
+    MimicDealer,
+    // Never takes a card that could bust the hand: hits hard 11 or less and soft 17 or
+    // less, and never doubles, splits or surrenders.
+    NeverBust,
+}
+
+impl Strategy {
+    /// Picks an action for a hand against the dealer's upcard, choosing only from the
+    /// `legal` actions.
+    pub fn choose(self, hand: &Hand, dealer_upcard: Card, legal: &[Action]) -> Action {
+        let value = hand.value();
+        let hit = match self {
+            Strategy::Basic => return basic_strategy(hand, dealer_upcard, legal),
+            Strategy::MimicDealer => value.total < 17,
+            Strategy::NeverBust => value.total <= 11 || (value.soft && value.total <= 17),
+        };
+        if hit && legal.contains(&Action::Hit) {
+            Action::Hit
+        } else {
+            Action::Stand
+        }
+    }
+}
+
+/// Picks an action for a hand using basic strategy against the dealer's upcard,
+/// choosing only from the `legal` actions.
+pub fn basic_strategy(hand: &Hand, dealer_upcard: Card, legal: &[Action]) -> Action {
+    let value = hand.value();
+    let total = value.total;
+    let upcard = dealer_upcard.value();
+
+    if hand.is_pair() && legal.contains(&Action::Split) {
+        // Always split Aces and eights, never fives or tens.
+        let split = match hand.cards()[0].rank {
+            Rank::Ace | Rank::Eight => true,
+            Rank::Two | Rank::Three | Rank::Seven => upcard <= 7,
+            Rank::Four => (5..=6).contains(&upcard),
+            Rank::Six => upcard <= 6,
+            Rank::Nine => upcard <= 9 && upcard != 7,
+            _ => false,
+        };
+        if split {
+            return Action::Split;
+        }
+    }
+
+    // Hard 16 gives up against a 9, 10 or Ace and hard 15 against a 10.
+    let surrender = !value.soft
+        && match total {
+            16 => upcard >= 9,
+            15 => upcard == 10,
+            _ => false,
+        };
+    if surrender && legal.contains(&Action::Surrender) {
+        return Action::Surrender;
+    }
+
+    let double = if value.soft {
+        // Soft 13 to 18 double against a weak dealer card.
+        match total {
+            13..=14 => (5..=6).contains(&upcard),
+            15..=16 => (4..=6).contains(&upcard),
+            17..=18 => (3..=6).contains(&upcard),
+            _ => false,
+        }
+    } else {
+        match total {
+            9 => (3..=6).contains(&upcard),
+            10 => upcard <= 9,
+            11 => upcard <= 10,
+            _ => false,
+        }
+    };
+    if double && legal.contains(&Action::Double) {
+        return Action::Double;
+    }
+
+    let hit = if value.soft {
+        // Soft hands: hit up to soft 17, and hit soft 18 against a 9, 10 or Ace.
+        total <= 17 || (total == 18 && upcard >= 9)
+    } else {
+        match total {
+            0..=11 => true,
+            12 => !(4..=6).contains(&upcard),
+            13..=16 => upcard >= 7,
+            _ => false,
+        }
+    };
+
+    if hit && legal.contains(&Action::Hit) {
+        Action::Hit
+    } else {
+        Action::Stand
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::{Rank, Suit};
+
+    const HIT_STAND: &[Action] = &[Action::Hit, Action::Stand];
+    const WITH_DOUBLE: &[Action] = &[Action::Hit, Action::Stand, Action::Double];
+
+    fn card(rank: Rank) -> Card {
+        Card::new(rank, Suit::Spades)
+    }
+
+    #[test]
+    fn test_strategies() {
+        let hand =
+            |cards: &[Rank]| Hand::from(cards.iter().map(|&rank| card(rank)).collect::<Vec<_>>());
+        let legal = &[Action::Hit, Action::Stand, Action::Double, Action::Split];
+        // (strategy, hand, dealer upcard, action)
+        let cases = [
+            // Basic strategy follows the chart.
+            (
+                Strategy::Basic,
+                hand(&[Rank::Eight, Rank::Eight]),
+                Rank::Ten,
+                Action::Split,
+            ),
+            (
+                Strategy::Basic,
+                hand(&[Rank::Ten, Rank::Three]),
+                Rank::Six,
+                Action::Stand,
+            ),
+            // Mimicking the dealer hits to 17 whatever the upcard...
+            (
+                Strategy::MimicDealer,
+                hand(&[Rank::Ten, Rank::Six]),
+                Rank::Six,
+                Action::Hit,
+            ),
+            (
+                Strategy::MimicDealer,
+                hand(&[Rank::Ten, Rank::Seven]),
+                Rank::Ace,
+                Action::Stand,
+            ),
+            // ...and never splits or doubles.
+            (
+                Strategy::MimicDealer,
+                hand(&[Rank::Eight, Rank::Eight]),
+                Rank::Six,
+                Action::Hit,
+            ),
+            (
+                Strategy::MimicDealer,
+                hand(&[Rank::Five, Rank::Six]),
+                Rank::Six,
+                Action::Hit,
+            ),
+            // Never busting stands on hard 12 but hits a soft 17.
+            (
+                Strategy::NeverBust,
+                hand(&[Rank::Ten, Rank::Two]),
+                Rank::Ten,
+                Action::Stand,
+            ),
+            (
+                Strategy::NeverBust,
+                hand(&[Rank::Five, Rank::Six]),
+                Rank::Ten,
+                Action::Hit,
+            ),
+            (
+                Strategy::NeverBust,
+                hand(&[Rank::Ace, Rank::Six]),
+                Rank::Ten,
+                Action::Hit,
+            ),
+            (
+                Strategy::NeverBust,
+                hand(&[Rank::Ace, Rank::Seven]),
+                Rank::Ten,
+                Action::Stand,
+            ),
+        ];
+        for (strategy, hand, upcard, action) in cases {
+            assert_eq!(
+                strategy.choose(&hand, card(upcard), legal),
+                action,
+                "{:?} with {:?}",
+                strategy,
+                hand
+            );
+        }
+
+        // Without a legal hit the hand stands.
+        assert_eq!(
+            Strategy::MimicDealer.choose(
+                &hand(&[Rank::Two, Rank::Three]),
+                card(Rank::Two),
+                &[Action::Stand]
+            ),
+            Action::Stand
+        );
+    }
+
+    #[test]
+    fn test_hard_totals() {
+        // Always hit 11 or less when doubling is not allowed.
+        let hand = Hand::from(vec![card(Rank::Five), card(Rank::Six)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Six), HIT_STAND),
+            Action::Hit
+        );
+
+        // 12 stands only against a 4, 5 or 6.
+        let hand = Hand::from(vec![card(Rank::Ten), card(Rank::Two)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Three), HIT_STAND),
+            Action::Hit
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Four), HIT_STAND),
+            Action::Stand
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Seven), HIT_STAND),
+            Action::Hit
+        );
+
+        // 13 to 16 stands against a dealer 2 through 6.
+        let hand = Hand::from(vec![card(Rank::Ten), card(Rank::Six)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Two), HIT_STAND),
+            Action::Stand
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::King), HIT_STAND),
+            Action::Hit
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ace), HIT_STAND),
+            Action::Hit
+        );
+
+        // 17 and up always stands.
+        let hand = Hand::from(vec![card(Rank::Ten), card(Rank::Seven)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ace), HIT_STAND),
+            Action::Stand
+        );
+    }
+
+    #[test]
+    fn test_soft_totals() {
+        // Soft 17 hits.
+        let hand = Hand::from(vec![card(Rank::Ace), card(Rank::Six)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Five), HIT_STAND),
+            Action::Hit
+        );
+
+        // Soft 18 stands against a 2 through 8 and hits against a 9, 10 or Ace.
+        let hand = Hand::from(vec![card(Rank::Ace), card(Rank::Seven)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Eight), HIT_STAND),
+            Action::Stand
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Nine), HIT_STAND),
+            Action::Hit
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ace), HIT_STAND),
+            Action::Hit
+        );
+
+        // Two Aces and a 9 is a soft 21.
+        let hand = Hand::from(vec![card(Rank::Ace), card(Rank::Ace), card(Rank::Nine)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ten), HIT_STAND),
+            Action::Stand
+        );
+
+        // Once the Ace has to count as 1 the hand is played as a hard total.
+        let hand = Hand::from(vec![card(Rank::Ace), card(Rank::Six), card(Rank::Nine)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ten), HIT_STAND),
+            Action::Hit
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Five), HIT_STAND),
+            Action::Stand
+        );
+    }
+
+    #[test]
+    fn test_doubles() {
+        // 11 doubles against anything but an Ace.
+        let hand = Hand::from(vec![card(Rank::Five), card(Rank::Six)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ten), WITH_DOUBLE),
+            Action::Double
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ace), WITH_DOUBLE),
+            Action::Hit
+        );
+
+        // 10 doubles against 2 through 9, 9 against 3 through 6.
+        let hand = Hand::from(vec![card(Rank::Four), card(Rank::Six)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Nine), WITH_DOUBLE),
+            Action::Double
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ten), WITH_DOUBLE),
+            Action::Hit
+        );
+        let hand = Hand::from(vec![card(Rank::Four), card(Rank::Five)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Three), WITH_DOUBLE),
+            Action::Double
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Two), WITH_DOUBLE),
+            Action::Hit
+        );
+
+        // Soft hands double against a weak upcard.
+        let hand = Hand::from(vec![card(Rank::Ace), card(Rank::Two)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Five), WITH_DOUBLE),
+            Action::Double
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Four), WITH_DOUBLE),
+            Action::Hit
+        );
+
+        // Soft 18 doubles against a 3 through 6, and stands there when it can't.
+        let hand = Hand::from(vec![card(Rank::Ace), card(Rank::Seven)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Six), WITH_DOUBLE),
+            Action::Double
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Six), HIT_STAND),
+            Action::Stand
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Two), WITH_DOUBLE),
+            Action::Stand
+        );
+
+        // 12 never doubles.
+        let hand = Hand::from(vec![card(Rank::Ten), card(Rank::Two)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Five), WITH_DOUBLE),
+            Action::Stand
+        );
+    }
+
+    #[test]
+    fn test_splits() {
+        let legal = &[Action::Hit, Action::Stand, Action::Double, Action::Split];
+        let pair = |rank| Hand::from(vec![card(rank), card(rank)]);
+
+        assert_eq!(
+            basic_strategy(&pair(Rank::Ace), card(Rank::Ten), legal),
+            Action::Split
+        );
+        assert_eq!(
+            basic_strategy(&pair(Rank::Eight), card(Rank::Ace), legal),
+            Action::Split
+        );
+        assert_eq!(
+            basic_strategy(&pair(Rank::Nine), card(Rank::Six), legal),
+            Action::Split
+        );
+        assert_eq!(
+            basic_strategy(&pair(Rank::Nine), card(Rank::Seven), legal),
+            Action::Stand
+        );
+
+        // Fives are played as a 10 and tens as a 20.
+        assert_eq!(
+            basic_strategy(&pair(Rank::Five), card(Rank::Six), legal),
+            Action::Double
+        );
+        assert_eq!(
+            basic_strategy(&pair(Rank::Ten), card(Rank::Six), legal),
+            Action::Stand
+        );
+
+        // A pair that can't be split is played by its total.
+        assert_eq!(
+            basic_strategy(&pair(Rank::Eight), card(Rank::Ten), HIT_STAND),
+            Action::Hit
+        );
+
+        // Split Aces that can't be hit stand.
+        let aces = &[Action::Stand, Action::Split];
+        let hand = Hand::from(vec![card(Rank::Ace), card(Rank::Five)]);
+        assert_eq!(basic_strategy(&hand, card(Rank::Ten), aces), Action::Stand);
+    }
+
+    #[test]
+    fn test_surrender() {
+        let legal = &[Action::Hit, Action::Stand, Action::Split, Action::Surrender];
+
+        // Hard 16 surrenders against a 9, 10 or Ace.
+        let hand = Hand::from(vec![card(Rank::Ten), card(Rank::Six)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Nine), legal),
+            Action::Surrender
+        );
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ace), legal),
+            Action::Surrender
+        );
+        assert_eq!(basic_strategy(&hand, card(Rank::Eight), legal), Action::Hit);
+
+        // Hard 15 only against a 10.
+        let hand = Hand::from(vec![card(Rank::Ten), card(Rank::Five)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::King), legal),
+            Action::Surrender
+        );
+        assert_eq!(basic_strategy(&hand, card(Rank::Ace), legal), Action::Hit);
+
+        // Eights are split rather than surrendered, and nothing is surrendered where
+        // the table doesn't allow it.
+        let hand = Hand::from(vec![card(Rank::Eight), card(Rank::Eight)]);
+        assert_eq!(basic_strategy(&hand, card(Rank::Ten), legal), Action::Split);
+        let hand = Hand::from(vec![card(Rank::Ten), card(Rank::Six)]);
+        assert_eq!(
+            basic_strategy(&hand, card(Rank::Ten), HIT_STAND),
+            Action::Hit
+        );
+    }
+}